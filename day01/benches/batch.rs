@@ -0,0 +1,57 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use day_1::batch::{evaluate, evaluate_parallel};
+use day_1::dial::{Dial, DialDirection, ZeroCountingPolicy, ZeroTally};
+use std::hint::black_box;
+
+const NUMBER_OF_INSTRUCTIONS: usize = 1_000_000;
+
+fn instructions() -> Vec<DialDirection> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    (0..NUMBER_OF_INSTRUCTIONS)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let steps = state % 1_000;
+            if state & (1 << 40) == 0 {
+                DialDirection::Left(steps)
+            } else {
+                DialDirection::Right(steps)
+            }
+        })
+        .collect()
+}
+
+fn sequential_loop(dial: &Dial, instructions: &[DialDirection]) -> ZeroTally {
+    let mut dial_result = dial.initial_result();
+    let mut zero_tally = ZeroTally::default();
+    for dial_direction in instructions {
+        dial_result =
+            dial.new_dial_position(dial_result, *dial_direction, ZeroCountingPolicy::Both);
+        zero_tally.add(&dial_result).unwrap();
+    }
+    zero_tally
+}
+
+fn bench_batch(c: &mut Criterion) {
+    let dial = Dial::default();
+    let instructions = instructions();
+
+    let mut group = c.benchmark_group("day01 batch");
+    group.sample_size(20);
+    group.bench_function("new_dial_position loop", |b| {
+        b.iter(|| sequential_loop(&dial, black_box(&instructions)))
+    });
+    group.bench_function("evaluate", |b| {
+        b.iter(|| evaluate(&dial, dial.start(), black_box(&instructions)).unwrap())
+    });
+    group.bench_function("evaluate_parallel", |b| {
+        b.iter(|| {
+            evaluate_parallel(&dial, dial.start(), black_box(&instructions), 64 * 1024).unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_batch);
+criterion_main!(benches);
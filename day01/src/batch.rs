@@ -0,0 +1,273 @@
+//! Evaluates a whole slice of instructions at once instead of threading a
+//! `DialResult` through `new_dial_position` one instruction at a time.
+//!
+//! Each instruction is reduced to a closed-form step on plain integers:
+//! the zeros passed are the multiples of the dial size the turn sweeps over,
+//! so no `DialResult` is built and only the running totals are checked for
+//! overflow.
+//!
+//! The parallel version splits the slice into chunks. A chunk's zero count
+//! depends on where the dial is when the chunk starts, so it first sums the
+//! net displacement of every chunk in parallel, turns those into the start
+//! position of each chunk with a prefix sum, and then evaluates the chunks in
+//! parallel from their known start positions. The result is exactly the
+//! sequential one.
+
+use crate::dial::{Dial, DialDirection, DialError, turn};
+use rayon::prelude::*;
+
+/// What a slice of instructions does to the dial as a whole.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BatchSummary {
+    /// Sum of the right turns minus the sum of the left turns.
+    pub net_displacement: i128,
+    pub final_position: u64,
+    pub number_of_zeros_crossed: u64,
+    pub number_of_zeros_landed_on: u64,
+}
+
+fn signed_steps(dial_direction: &DialDirection) -> i128 {
+    match *dial_direction {
+        DialDirection::Left(steps) => -(steps as i128),
+        DialDirection::Right(steps) => steps as i128,
+    }
+}
+
+fn net_displacement(instructions: &[DialDirection]) -> Result<i128, DialError> {
+    instructions
+        .iter()
+        .try_fold(0_i128, |total, dial_direction| {
+            total
+                .checked_add(signed_steps(dial_direction))
+                .ok_or(DialError::Overflow)
+        })
+}
+
+fn checked_sum(left: BatchSummary, right: BatchSummary) -> Result<BatchSummary, DialError> {
+    Ok(BatchSummary {
+        net_displacement: left
+            .net_displacement
+            .checked_add(right.net_displacement)
+            .ok_or(DialError::Overflow)?,
+        final_position: right.final_position,
+        number_of_zeros_crossed: left
+            .number_of_zeros_crossed
+            .checked_add(right.number_of_zeros_crossed)
+            .ok_or(DialError::Overflow)?,
+        number_of_zeros_landed_on: left
+            .number_of_zeros_landed_on
+            .checked_add(right.number_of_zeros_landed_on)
+            .ok_or(DialError::Overflow)?,
+    })
+}
+
+/// Evaluates `instructions` in order, starting from `start`.
+/// Matches applying `Dial::new_dial_position` to every instruction with the
+/// `Both` policy and summing the zero counts.
+pub fn evaluate(
+    dial: &Dial,
+    start: u64,
+    instructions: &[DialDirection],
+) -> Result<BatchSummary, DialError> {
+    assert!(start <= dial.maximum_position());
+
+    let size = dial.size();
+    let mut position = start;
+    let mut number_of_zeros_crossed: u64 = 0;
+    let mut number_of_zeros_landed_on: u64 = 0;
+    for dial_direction in instructions {
+        let (next_position, crossed) = turn(size, position, *dial_direction);
+        number_of_zeros_crossed = number_of_zeros_crossed
+            .checked_add(crossed)
+            .ok_or(DialError::Overflow)?;
+        number_of_zeros_landed_on += (next_position == 0) as u64;
+        position = next_position;
+    }
+
+    Ok(BatchSummary {
+        net_displacement: net_displacement(instructions)?,
+        final_position: position,
+        number_of_zeros_crossed,
+        number_of_zeros_landed_on,
+    })
+}
+
+/// Same result as `evaluate`, computed on all cores in chunks of `chunk_size`
+/// instructions.
+pub fn evaluate_parallel(
+    dial: &Dial,
+    start: u64,
+    instructions: &[DialDirection],
+    chunk_size: usize,
+) -> Result<BatchSummary, DialError> {
+    assert!(chunk_size > 0, "chunk size should be greater than 0");
+    assert!(start <= dial.maximum_position());
+
+    let displacements: Vec<i128> = instructions
+        .par_chunks(chunk_size)
+        .map(net_displacement)
+        .collect::<Result<_, _>>()?;
+
+    let size = dial.size() as i128;
+    let mut chunk_starts = Vec::with_capacity(displacements.len());
+    let mut position = start as i128;
+    for displacement in &displacements {
+        chunk_starts.push(position as u64);
+        position = (position + displacement.rem_euclid(size)).rem_euclid(size);
+    }
+
+    let summaries: Vec<BatchSummary> = instructions
+        .par_chunks(chunk_size)
+        .zip(chunk_starts)
+        .map(|(chunk, chunk_start)| evaluate(dial, chunk_start, chunk))
+        .collect::<Result<_, _>>()?;
+
+    summaries.into_iter().try_fold(
+        BatchSummary {
+            net_displacement: 0,
+            final_position: start,
+            number_of_zeros_crossed: 0,
+            number_of_zeros_landed_on: 0,
+        },
+        checked_sum,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dial::{ZeroCountingPolicy, ZeroTally};
+
+    fn sample_instructions() -> Vec<DialDirection> {
+        use DialDirection::{Left, Right};
+        vec![
+            Left(68),
+            Left(30),
+            Right(48),
+            Left(5),
+            Right(60),
+            Left(55),
+            Left(1),
+            Left(99),
+            Right(14),
+            Left(82),
+        ]
+    }
+
+    /// The one-instruction-at-a-time loop that the batch functions must match.
+    fn sequential(dial: &Dial, instructions: &[DialDirection]) -> BatchSummary {
+        let mut dial_result = dial.initial_result();
+        let mut zero_tally = ZeroTally::default();
+        for dial_direction in instructions {
+            dial_result =
+                dial.new_dial_position(dial_result, *dial_direction, ZeroCountingPolicy::Both);
+            zero_tally.add(&dial_result).unwrap();
+        }
+        BatchSummary {
+            net_displacement: net_displacement(instructions).unwrap(),
+            final_position: dial_result.dial_position,
+            number_of_zeros_crossed: zero_tally.crossed,
+            number_of_zeros_landed_on: zero_tally.landed_on,
+        }
+    }
+
+    /// Deterministic pseudo-random instructions, so the tests need no extra dependency.
+    fn generated_instructions(count: usize, maximum_steps: u64) -> Vec<DialDirection> {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        (0..count)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                let steps = state % (maximum_steps + 1);
+                if state & (1 << 40) == 0 {
+                    DialDirection::Left(steps)
+                } else {
+                    DialDirection::Right(steps)
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_evaluate_case_1() {
+        let dial = Dial::default();
+        let summary = evaluate(&dial, 50, &sample_instructions()).unwrap();
+        assert_eq!(summary.net_displacement, -218);
+        assert_eq!(summary.final_position, 32);
+        assert_eq!(summary.number_of_zeros_crossed, 6);
+        assert_eq!(summary.number_of_zeros_landed_on, 3);
+    }
+
+    #[test]
+    fn test_evaluate_case_2() {
+        let dial = Dial::default();
+        let summary = evaluate(&dial, 50, &[]).unwrap();
+        assert_eq!(summary.final_position, 50);
+        assert_eq!(summary.number_of_zeros_crossed, 0);
+    }
+
+    #[test]
+    fn test_evaluate_case_3() {
+        let dial = Dial::new(1000, 0).unwrap();
+        let instructions = generated_instructions(5_000, 5_000);
+        assert_eq!(
+            evaluate(&dial, 0, &instructions).unwrap(),
+            sequential(&dial, &instructions)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_case_4() {
+        let dial = Dial::new(u64::MAX, u64::MAX - 1).unwrap();
+        let instructions = vec![
+            DialDirection::Right(u64::MAX),
+            DialDirection::Left(u64::MAX),
+        ];
+        assert_eq!(
+            evaluate(&dial, u64::MAX - 1, &instructions).unwrap(),
+            sequential(&dial, &instructions)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_parallel_case_1() {
+        let dial = Dial::default();
+        for chunk_size in 1..=11 {
+            assert_eq!(
+                evaluate_parallel(&dial, 50, &sample_instructions(), chunk_size).unwrap(),
+                sequential(&dial, &sample_instructions())
+            );
+        }
+    }
+
+    #[test]
+    fn test_evaluate_parallel_case_2() {
+        let dial = Dial::new(3600, 1800).unwrap();
+        let instructions = generated_instructions(20_000, 10_000);
+        assert_eq!(
+            evaluate_parallel(&dial, 1800, &instructions, 333).unwrap(),
+            sequential(&dial, &instructions)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_parallel_case_3() {
+        let dial = Dial::new(1 << 40, 0).unwrap();
+        let instructions = generated_instructions(1_000, u64::MAX - 1);
+        assert_eq!(
+            evaluate_parallel(&dial, 0, &instructions, 64).unwrap(),
+            sequential(&dial, &instructions)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_parallel_case_4() {
+        let dial = Dial::new(1, 0).unwrap();
+        let instructions = vec![DialDirection::Right(u64::MAX), DialDirection::Right(1)];
+        assert_eq!(
+            evaluate_parallel(&dial, 0, &instructions, 1),
+            Err(DialError::Overflow)
+        );
+    }
+}
@@ -0,0 +1,726 @@
+use std::fmt;
+
+/// The dial direction is either Left or Right with a number of steps to turn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DialDirection {
+    Left(u64),
+    Right(u64),
+}
+
+impl fmt::Display for DialDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DialDirection::Left(steps) => write!(f, "L{}", steps),
+            DialDirection::Right(steps) => write!(f, "R{}", steps),
+        }
+    }
+}
+
+/// Which events count as "hitting zero".
+/// `LandsOn` counts instructions that finish at 0, `Passes` counts every
+/// click that points at 0 during an instruction, and `Both` reports both.
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum ZeroCountingPolicy {
+    LandsOn,
+    Passes,
+    Both,
+}
+
+impl ZeroCountingPolicy {
+    fn counts_lands_on(self) -> bool {
+        matches!(self, ZeroCountingPolicy::LandsOn | ZeroCountingPolicy::Both)
+    }
+
+    fn counts_passes(self) -> bool {
+        matches!(self, ZeroCountingPolicy::Passes | ZeroCountingPolicy::Both)
+    }
+
+    /// Clears the zero counts in `dial_result` that this policy does not ask for.
+    pub fn apply(self, mut dial_result: DialResult) -> DialResult {
+        if !self.counts_passes() {
+            dial_result.number_of_zeros_crossed = 0;
+        }
+        if !self.counts_lands_on() {
+            dial_result.number_of_zeros_landed_on = 0;
+        }
+        dial_result
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DialResult {
+    pub dial_position: u64,
+    pub number_of_zeros_crossed: u64,
+    pub number_of_zeros_landed_on: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DialError {
+    ZeroSize,
+    StartOutOfRange {
+        start: u64,
+        size: u64,
+    },
+    Overflow,
+    NoWheels,
+    WheelOutOfRange {
+        wheel: usize,
+        number_of_wheels: usize,
+    },
+}
+
+impl fmt::Display for DialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DialError::ZeroSize => write!(f, "dial size should be greater than 0"),
+            DialError::StartOutOfRange { start, size } => write!(
+                f,
+                "start position {} should be less than the dial size {}",
+                start, size
+            ),
+            DialError::Overflow => {
+                write!(f, "arithmetic overflow while adding up the instructions")
+            }
+            DialError::NoWheels => write!(f, "a lock needs at least one wheel"),
+            DialError::WheelOutOfRange {
+                wheel,
+                number_of_wheels,
+            } => write!(
+                f,
+                "wheel {} does not exist, wheels are numbered 1 to {}",
+                wheel, number_of_wheels
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DialError {}
+
+/// A dial with `size` positions, numbered `0` to `size - 1`, that starts
+/// at position `start`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dial {
+    size: u64,
+    start: u64,
+}
+
+impl Default for Dial {
+    fn default() -> Self {
+        Dial {
+            size: 100,
+            start: 50,
+        }
+    }
+}
+
+impl Dial {
+    pub fn new(size: u64, start: u64) -> Result<Dial, DialError> {
+        if size == 0 {
+            return Err(DialError::ZeroSize);
+        }
+        if start >= size {
+            return Err(DialError::StartOutOfRange { start, size });
+        }
+        Ok(Dial { size, start })
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    pub fn maximum_position(&self) -> u64 {
+        self.size - 1
+    }
+
+    /// The result before any instruction has been applied.
+    pub fn initial_result(&self) -> DialResult {
+        DialResult {
+            dial_position: self.start,
+            number_of_zeros_crossed: 0,
+            number_of_zeros_landed_on: 0,
+        }
+    }
+
+    /// Applies one instruction and keeps only the zero counts that `policy` asks for.
+    pub fn new_dial_position(
+        &self,
+        current_dial_position: DialResult,
+        dial_direction: DialDirection,
+        policy: ZeroCountingPolicy,
+    ) -> DialResult {
+        let dial_result = match dial_direction {
+            DialDirection::Left(steps) => self.dial_left(current_dial_position, steps),
+            DialDirection::Right(steps) => self.dial_right(current_dial_position, steps),
+        };
+        policy.apply(dial_result)
+    }
+
+    pub fn dial_right(&self, current_dial_position: DialResult, steps: u64) -> DialResult {
+        self.apply_turn(current_dial_position, DialDirection::Right(steps))
+    }
+
+    /// Calculates the new dial position when dialling left
+    pub fn dial_left(&self, current_dial_position: DialResult, steps: u64) -> DialResult {
+        self.apply_turn(current_dial_position, DialDirection::Left(steps))
+    }
+
+    fn apply_turn(
+        &self,
+        current_dial_position: DialResult,
+        dial_direction: DialDirection,
+    ) -> DialResult {
+        assert!(current_dial_position.dial_position <= self.maximum_position());
+
+        let (dial_position, number_of_zeros_crossed) = turn(
+            self.size,
+            current_dial_position.dial_position,
+            dial_direction,
+        );
+        DialResult {
+            dial_position,
+            number_of_zeros_crossed,
+            number_of_zeros_landed_on: (dial_position == 0) as u64,
+        }
+    }
+}
+
+/// Turns a dial of `size` positions once from `position` (already reduced
+/// modulo `size`) and returns the new position and the zeros passed on the
+/// way, the one it lands on included.
+/// The count never exceeds the number of steps, so it always fits in a u64.
+pub(crate) fn turn(size: u64, position: u64, dial_direction: DialDirection) -> (u64, u64) {
+    match dial_direction {
+        DialDirection::Right(steps) => match position.checked_add(steps) {
+            Some(unwrapped) => (unwrapped % size, unwrapped / size),
+            None => {
+                // Position and steps are both u64, so their sum always fits in a u128.
+                let unwrapped = position as u128 + steps as u128;
+                let size = size as u128;
+                ((unwrapped % size) as u64, (unwrapped / size) as u64)
+            }
+        },
+        DialDirection::Left(steps) if steps < position => (position - steps, 0),
+        DialDirection::Left(steps) => {
+            // `overshoot` is how far past position 0 we land, counting leftwards.
+            // The dial reaches 0 after `position` steps, unless it starts
+            // there, and once more every full turn after that.
+            let overshoot = steps - position;
+            let crossed = overshoot / size + (position != 0) as u64;
+            ((size - overshoot % size) % size, crossed)
+        }
+    }
+}
+
+/// Running totals of the zero counts over a sequence of instructions.
+#[derive(Debug, Default, PartialEq)]
+pub struct ZeroTally {
+    pub landed_on: u64,
+    pub crossed: u64,
+}
+
+impl ZeroTally {
+    /// Adds the zeros counted by a single instruction to the running totals.
+    pub fn add(&mut self, dial_result: &DialResult) -> Result<(), DialError> {
+        self.landed_on = self
+            .landed_on
+            .checked_add(dial_result.number_of_zeros_landed_on)
+            .ok_or(DialError::Overflow)?;
+        self.crossed = self
+            .crossed
+            .checked_add(dial_result.number_of_zeros_crossed)
+            .ok_or(DialError::Overflow)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn dial_at(dial_position: u64) -> DialResult {
+        DialResult {
+            dial_position,
+            number_of_zeros_crossed: 0,
+            number_of_zeros_landed_on: 0,
+        }
+    }
+
+    /// Reference implementation: turns the dial one click at a time and counts
+    /// every click that arrives at 0.
+    fn oracle(size: u64, dial_position: u64, dial_direction: DialDirection) -> DialResult {
+        let (steps, right) = match dial_direction {
+            DialDirection::Left(steps) => (steps, false),
+            DialDirection::Right(steps) => (steps, true),
+        };
+        let mut dial_position = dial_position;
+        let mut number_of_zeros_crossed = 0;
+        for _ in 0..steps {
+            dial_position = if right {
+                (dial_position + 1) % size
+            } else {
+                (dial_position + size - 1) % size
+            };
+            if dial_position == 0 {
+                number_of_zeros_crossed += 1;
+            }
+        }
+        DialResult {
+            dial_position,
+            number_of_zeros_crossed,
+            number_of_zeros_landed_on: (dial_position == 0) as u64,
+        }
+    }
+
+    /// A dial size, a position on it and a number of steps small enough for the oracle.
+    fn small_turn() -> impl Strategy<Value = (u64, u64, u64)> {
+        (1..=500_u64).prop_flat_map(|size| (Just(size), 0..size, 0..=size * 5))
+    }
+
+    proptest! {
+        #[test]
+        fn test_dial_left_matches_oracle((size, dial_position, steps) in small_turn()) {
+            let dial = Dial::new(size, 0).unwrap();
+            prop_assert_eq!(
+                dial.dial_left(dial_at(dial_position), steps),
+                oracle(size, dial_position, DialDirection::Left(steps))
+            );
+        }
+
+        #[test]
+        fn test_dial_right_matches_oracle((size, dial_position, steps) in small_turn()) {
+            let dial = Dial::new(size, 0).unwrap();
+            prop_assert_eq!(
+                dial.dial_right(dial_at(dial_position), steps),
+                oracle(size, dial_position, DialDirection::Right(steps))
+            );
+        }
+
+        /// Splitting a turn in two passes the same zeros, so the closed form can be
+        /// checked on sizes and step counts far beyond the oracle's reach.
+        #[test]
+        fn test_dial_turns_are_additive(
+            size in 1..=u64::MAX,
+            dial_position in any::<u64>(),
+            first in 0..=u64::MAX / 2,
+            second in 0..=u64::MAX / 2,
+            right in any::<bool>(),
+        ) {
+            let dial = Dial::new(size, 0).unwrap();
+            let dial_position = dial_position % size;
+            let direction = if right { DialDirection::Right } else { DialDirection::Left };
+            let turn = |dial_position, steps| {
+                dial.new_dial_position(dial_at(dial_position), direction(steps), ZeroCountingPolicy::Both)
+            };
+
+            let whole = turn(dial_position, first + second);
+            let halfway = turn(dial_position, first);
+            let rest = turn(halfway.dial_position, second);
+            prop_assert_eq!(whole.dial_position, rest.dial_position);
+            prop_assert_eq!(
+                whole.number_of_zeros_crossed,
+                halfway.number_of_zeros_crossed + rest.number_of_zeros_crossed
+            );
+        }
+    }
+
+    #[test]
+    fn test_dial_new_case_1() {
+        assert_eq!(Dial::new(0, 0), Err(DialError::ZeroSize));
+    }
+
+    #[test]
+    fn test_dial_new_case_2() {
+        assert_eq!(
+            Dial::new(256, 256),
+            Err(DialError::StartOutOfRange {
+                start: 256,
+                size: 256
+            })
+        );
+    }
+
+    #[test]
+    fn test_dial_new_case_3() {
+        let dial = Dial::new(3600, 1800).unwrap();
+        assert_eq!(dial.size, 3600);
+        assert_eq!(dial.start, 1800);
+        assert_eq!(dial.maximum_position(), 3599);
+    }
+
+    #[test]
+    fn test_dial_left_case_dial_before_zero_case_1() {
+        let dial = Dial::default();
+        let steps = 1;
+        let dial_result = dial.dial_left(dial_at(50), steps);
+        assert_eq!(dial_result.dial_position, 49);
+        assert_eq!(dial_result.number_of_zeros_crossed, 0);
+    }
+
+    #[test]
+    fn test_dial_left_case_dial_past_zero_case_1() {
+        let dial = Dial::default();
+        let steps = 51;
+        let dial_result = dial.dial_left(dial_at(50), steps);
+        assert_eq!(dial_result.dial_position, 99);
+        assert_eq!(dial_result.number_of_zeros_crossed, 1);
+    }
+
+    #[test]
+    fn test_dial_left_case_dial_at_zero_case_1() {
+        let dial = Dial::default();
+        let steps = 50;
+        let dial_result = dial.dial_left(dial_at(50), steps);
+        assert_eq!(dial_result.dial_position, 0);
+        assert_eq!(dial_result.number_of_zeros_crossed, 1);
+    }
+
+    #[test]
+    fn test_dial_left_case_dial_past_zero_case_2() {
+        let dial = Dial::default();
+        let steps = 52;
+        let dial_result = dial.dial_left(dial_at(50), steps);
+        assert_eq!(dial_result.dial_position, 98);
+        assert_eq!(dial_result.number_of_zeros_crossed, 1);
+    }
+
+    #[test]
+    fn test_dial_left_case_dial_past_zero_full_cycle_case_1() {
+        let dial = Dial::default();
+        let steps = 50 + 50;
+        let dial_result = dial.dial_left(dial_at(50), steps);
+        assert_eq!(dial_result.dial_position, 50);
+        assert_eq!(dial_result.number_of_zeros_crossed, 1);
+    }
+
+    #[test]
+    fn test_dial_left_case_dial_past_zero_full_cycle_case_2() {
+        let dial = Dial::default();
+        let steps = 50 + 50 + 2;
+        let dial_result = dial.dial_left(dial_at(50), steps);
+        assert_eq!(dial_result.dial_position, 48);
+        assert_eq!(dial_result.number_of_zeros_crossed, 1);
+    }
+
+    #[test]
+    fn test_dial_left_case_dial_past_zero_full_cycle_case_3() {
+        let dial = Dial::default();
+        let steps = 10 * 50 + 2;
+        let dial_result = dial.dial_left(dial_at(50), steps);
+        assert_eq!(dial_result.dial_position, 48);
+        assert_eq!(dial_result.number_of_zeros_crossed, 5);
+    }
+
+    #[test]
+    fn test_dial_left_case_dial_past_zero_full_cycle_case_4() {
+        let dial = Dial::default();
+        let steps: u64 = 6 * dial.size + 2;
+        let dial_result = dial.dial_left(dial_at(50), steps);
+        assert_eq!(dial_result.dial_position, 48);
+        assert_eq!(dial_result.number_of_zeros_crossed, 6);
+    }
+
+    #[test]
+    fn test_dial_left_case_dial_past_zero_full_cycle_case_5() {
+        let dial = Dial::default();
+        let steps: u64 = 6 * dial.size - 2;
+        let dial_result = dial.dial_left(dial_at(50), steps);
+        assert_eq!(dial_result.dial_position, 52);
+        assert_eq!(dial_result.number_of_zeros_crossed, 6);
+    }
+
+    #[test]
+    fn test_dial_left_case_6() {
+        let dial = Dial::default();
+        let steps = 68;
+        let dial_result = dial.dial_left(dial_at(50), steps);
+        assert_eq!(dial_result.dial_position, 82);
+        assert_eq!(dial_result.number_of_zeros_crossed, 1);
+    }
+
+    #[test]
+    fn test_dial_left_case_7() {
+        let dial = Dial::default();
+        let steps = 30;
+        let dial_result = dial.dial_left(dial_at(82), steps);
+        assert_eq!(dial_result.dial_position, 52);
+        assert_eq!(dial_result.number_of_zeros_crossed, 0);
+    }
+
+    #[test]
+    fn test_dial_left_case_8() {
+        let dial = Dial::default();
+        let steps = 5;
+        let dial_result = dial.dial_left(dial_at(0), steps);
+        assert_eq!(dial_result.dial_position, 95);
+        assert_eq!(dial_result.number_of_zeros_crossed, 0);
+    }
+
+    #[test]
+    fn test_dial_left_case_9() {
+        let dial = Dial::new(256, 0).unwrap();
+        let steps = 256 * 3;
+        let dial_result = dial.dial_left(dial_at(0), steps);
+        assert_eq!(dial_result.dial_position, 0);
+        assert_eq!(dial_result.number_of_zeros_crossed, 3);
+    }
+
+    #[test]
+    fn test_dial_left_case_10() {
+        let dial = Dial::new(3600, 0).unwrap();
+        let steps = 1000;
+        let dial_result = dial.dial_left(dial_at(900), steps);
+        assert_eq!(dial_result.dial_position, 3500);
+        assert_eq!(dial_result.number_of_zeros_crossed, 1);
+    }
+
+    #[test]
+    fn test_dial_left_case_11() {
+        let dial = Dial::new(u64::MAX, 0).unwrap();
+        let steps = 1;
+        let dial_result = dial.dial_left(dial_at(0), steps);
+        assert_eq!(dial_result.dial_position, u64::MAX - 1);
+        assert_eq!(dial_result.number_of_zeros_crossed, 0);
+    }
+
+    #[test]
+    fn test_dial_left_case_12() {
+        let dial = Dial::default();
+        let steps = u64::MAX;
+        let dial_result = dial.dial_left(dial_at(50), steps);
+        assert_eq!(dial_result.dial_position, 35);
+        assert_eq!(dial_result.number_of_zeros_crossed, 184467440737095516);
+    }
+
+    #[test]
+    fn test_dial_left_case_13() {
+        let dial = Dial::new(1, 0).unwrap();
+        let steps = u64::MAX;
+        let dial_result = dial.dial_left(dial_at(0), steps);
+        assert_eq!(dial_result.dial_position, 0);
+        assert_eq!(dial_result.number_of_zeros_crossed, u64::MAX);
+    }
+
+    //---------------- Right movement -------------------------------
+
+    #[test]
+    fn test_dial_right_case_1() {
+        let dial = Dial::default();
+        let steps = 1;
+        let dial_result = dial.dial_right(dial_at(50), steps);
+        assert_eq!(dial_result.dial_position, 51);
+        assert_eq!(dial_result.number_of_zeros_crossed, 0);
+    }
+
+    #[test]
+    fn test_dial_right_case_2() {
+        let dial = Dial::default();
+        let steps = 49;
+        let dial_result = dial.dial_right(dial_at(50), steps);
+        assert_eq!(dial_result.dial_position, 99);
+        assert_eq!(dial_result.number_of_zeros_crossed, 0);
+    }
+
+    #[test]
+    fn test_dial_right_case_3() {
+        let dial = Dial::default();
+        let steps = 50;
+        let dial_result = dial.dial_right(dial_at(50), steps);
+        assert_eq!(dial_result.dial_position, 0);
+        assert_eq!(dial_result.number_of_zeros_crossed, 1);
+    }
+
+    #[test]
+    fn test_dial_right_case_4() {
+        let dial = Dial::default();
+        let steps = 51;
+        let dial_result = dial.dial_right(dial_at(50), steps);
+        assert_eq!(dial_result.dial_position, 1);
+        assert_eq!(dial_result.number_of_zeros_crossed, 1);
+    }
+
+    #[test]
+    fn test_dial_right_case_5() {
+        let dial = Dial::default();
+        let steps = 151;
+        let dial_result = dial.dial_right(dial_at(50), steps);
+        assert_eq!(dial_result.dial_position, 1);
+        assert_eq!(dial_result.number_of_zeros_crossed, 2);
+    }
+
+    #[test]
+    fn test_dial_right_case_6() {
+        let dial = Dial::default();
+        let steps = 251;
+        let dial_result = dial.dial_right(dial_at(50), steps);
+        assert_eq!(dial_result.dial_position, 1);
+        assert_eq!(dial_result.number_of_zeros_crossed, 3);
+    }
+
+    #[test]
+    fn test_dial_right_case_7() {
+        let dial = Dial::default();
+        let steps = 48;
+        let dial_result = dial.dial_right(dial_at(52), steps);
+        assert_eq!(dial_result.dial_position, 0);
+        assert_eq!(dial_result.number_of_zeros_crossed, 1);
+    }
+
+    #[test]
+    fn test_dial_right_case_8() {
+        let dial = Dial::default();
+        let steps = 258;
+        let dial_result = dial.dial_right(dial_at(50), steps);
+        assert_eq!(dial_result.dial_position, 8);
+        assert_eq!(dial_result.number_of_zeros_crossed, 3);
+    }
+
+    #[test]
+    fn test_dial_right_case_9() {
+        let dial = Dial::default();
+        let steps = 90;
+        let dial_result = dial.dial_right(dial_at(10), steps);
+        assert_eq!(dial_result.dial_position, 0);
+        assert_eq!(dial_result.number_of_zeros_crossed, 1);
+    }
+
+    #[test]
+    fn test_dial_right_case_10() {
+        let dial = Dial::default();
+        let steps = 91;
+        let dial_result = dial.dial_right(dial_at(10), steps);
+        assert_eq!(dial_result.dial_position, 1);
+        assert_eq!(dial_result.number_of_zeros_crossed, 1);
+    }
+
+    #[test]
+    fn test_dial_right_case_11() {
+        let dial = Dial::new(1000, 0).unwrap();
+        let steps = 2500;
+        let dial_result = dial.dial_right(dial_at(999), steps);
+        assert_eq!(dial_result.dial_position, 499);
+        assert_eq!(dial_result.number_of_zeros_crossed, 3);
+    }
+
+    #[test]
+    fn test_dial_right_case_12() {
+        let dial = Dial::new(256, 0).unwrap();
+        let steps = 1;
+        let dial_result = dial.dial_right(dial_at(255), steps);
+        assert_eq!(dial_result.dial_position, 0);
+        assert_eq!(dial_result.number_of_zeros_crossed, 1);
+    }
+
+    #[test]
+    fn test_dial_right_case_13() {
+        let dial = Dial::new(u64::MAX, 0).unwrap();
+        let steps = u64::MAX;
+        let dial_result = dial.dial_right(dial_at(u64::MAX - 1), steps);
+        assert_eq!(dial_result.dial_position, u64::MAX - 1);
+        assert_eq!(dial_result.number_of_zeros_crossed, 1);
+    }
+
+    #[test]
+    fn test_dial_right_case_14() {
+        let dial = Dial::new(1, 0).unwrap();
+        let steps = u64::MAX;
+        let dial_result = dial.dial_right(dial_at(0), steps);
+        assert_eq!(dial_result.dial_position, 0);
+        assert_eq!(dial_result.number_of_zeros_crossed, u64::MAX);
+    }
+
+    // new_dial_position
+
+    #[test]
+    fn test_new_dial_position_case_1() {
+        let dial = Dial::default();
+        let dial_result = dial.new_dial_position(
+            dial_at(50),
+            DialDirection::Left(150),
+            ZeroCountingPolicy::Passes,
+        );
+        assert_eq!(dial_result.dial_position, 0);
+        assert_eq!(dial_result.number_of_zeros_crossed, 2);
+        assert_eq!(dial_result.number_of_zeros_landed_on, 0);
+    }
+
+    #[test]
+    fn test_new_dial_position_case_2() {
+        let dial = Dial::default();
+        let dial_result = dial.new_dial_position(
+            dial_at(50),
+            DialDirection::Left(150),
+            ZeroCountingPolicy::LandsOn,
+        );
+        assert_eq!(dial_result.dial_position, 0);
+        assert_eq!(dial_result.number_of_zeros_crossed, 0);
+        assert_eq!(dial_result.number_of_zeros_landed_on, 1);
+    }
+
+    #[test]
+    fn test_new_dial_position_case_3() {
+        let dial = Dial::default();
+        let dial_result = dial.new_dial_position(
+            dial_at(50),
+            DialDirection::Right(150),
+            ZeroCountingPolicy::Both,
+        );
+        assert_eq!(dial_result.dial_position, 0);
+        assert_eq!(dial_result.number_of_zeros_crossed, 2);
+        assert_eq!(dial_result.number_of_zeros_landed_on, 1);
+    }
+
+    #[test]
+    fn test_new_dial_position_case_4() {
+        let dial = Dial::default();
+        let dial_result = dial.new_dial_position(
+            dial_at(50),
+            DialDirection::Right(151),
+            ZeroCountingPolicy::Both,
+        );
+        assert_eq!(dial_result.dial_position, 1);
+        assert_eq!(dial_result.number_of_zeros_crossed, 2);
+        assert_eq!(dial_result.number_of_zeros_landed_on, 0);
+    }
+
+    // ZeroTally
+
+    #[test]
+    fn test_zero_tally_add_case_1() {
+        let mut tally = ZeroTally {
+            landed_on: 2,
+            crossed: 4,
+        };
+        let dial_result = DialResult {
+            dial_position: 0,
+            number_of_zeros_crossed: 3,
+            number_of_zeros_landed_on: 1,
+        };
+        assert_eq!(tally.add(&dial_result), Ok(()));
+        assert_eq!(
+            tally,
+            ZeroTally {
+                landed_on: 3,
+                crossed: 7
+            }
+        );
+    }
+
+    #[test]
+    fn test_zero_tally_add_case_2() {
+        let mut tally = ZeroTally {
+            landed_on: 0,
+            crossed: u64::MAX,
+        };
+        let dial_result = DialResult {
+            dial_position: 0,
+            number_of_zeros_crossed: 1,
+            number_of_zeros_landed_on: 1,
+        };
+        assert_eq!(tally.add(&dial_result), Err(DialError::Overflow));
+    }
+}
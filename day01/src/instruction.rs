@@ -0,0 +1,723 @@
+//! Grammar of a line of day01 input:
+//!
+//! ```text
+//! line      = [wheel ":"] [repeat "x"] direction ["+"] steps ["#" comment]
+//! direction = "L" | "R" | "CCW" | "CW"      (any case; CCW is L, CW is R)
+//! wheel, repeat, steps = decimal digits
+//! ```
+//!
+//! Whitespace may appear between any two parts. Lines that are blank or hold
+//! only a comment are skipped. `3xL10` turns the dial left by 10 three times;
+//! the repeat count is at most `MAXIMUM_REPEAT`.
+
+use crate::dial::DialDirection;
+use crate::lock::WheelInstruction;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::iter::Peekable;
+use std::num::IntErrorKind;
+use std::str::Chars;
+
+/// Largest repeat count accepted in `NxINSTR`. Every repetition is applied
+/// one at a time, so larger counts would keep the program busy for ages.
+pub const MAXIMUM_REPEAT: u64 = 1_000_000;
+
+#[derive(Debug, PartialEq)]
+pub enum ParseErrorReason {
+    MissingDirection,
+    InvalidDirection(String),
+    MissingSteps,
+    InvalidStepsCharacter(char),
+    StepsOutOfRange,
+    InvalidWheel,
+    InvalidRepeat,
+    RepeatOutOfRange,
+    InvalidUtf8,
+    UnexpectedNumber,
+    UnexpectedCharacter(char),
+}
+
+impl fmt::Display for ParseErrorReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorReason::MissingDirection => write!(f, "missing dial direction"),
+            ParseErrorReason::InvalidDirection(direction) => write!(
+                f,
+                "invalid dial direction '{}', expected L, R, CCW or CW",
+                direction
+            ),
+            ParseErrorReason::MissingSteps => write!(f, "missing number of steps"),
+            ParseErrorReason::InvalidStepsCharacter(c) => {
+                write!(f, "invalid character '{}' in number of steps", c)
+            }
+            ParseErrorReason::StepsOutOfRange => {
+                write!(f, "number of steps is larger than {}", u64::MAX)
+            }
+            ParseErrorReason::InvalidWheel => {
+                write!(f, "wheel should be a number from 1 before ':'")
+            }
+            ParseErrorReason::InvalidRepeat => {
+                write!(f, "repeat count should be a number from 1 before 'x'")
+            }
+            ParseErrorReason::InvalidUtf8 => write!(f, "invalid UTF-8"),
+            ParseErrorReason::RepeatOutOfRange => {
+                write!(f, "repeat count is larger than {}", MAXIMUM_REPEAT)
+            }
+            ParseErrorReason::UnexpectedNumber => write!(
+                f,
+                "a number before the direction should be followed by ':' (wheel) or 'x' (repeat count)"
+            ),
+            ParseErrorReason::UnexpectedCharacter(c) => {
+                write!(f, "unexpected '{}' after the instruction", c)
+            }
+        }
+    }
+}
+
+/// Where and why a rotation instruction could not be parsed.
+/// `line` and `column` are 1-based; `column` counts characters, not bytes.
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub reason: ParseErrorReason,
+}
+
+impl ParseError {
+    pub fn at_line(self, line: usize) -> ParseError {
+        ParseError { line, ..self }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.reason
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// One line of input: an instruction and how many times in a row to apply it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Statement {
+    pub wheel_instruction: WheelInstruction,
+    pub repeat: u64,
+}
+
+/// Reads one line character by character, keeping track of the column.
+/// A `#` ends the line like the end of the input does.
+struct Scanner<'a> {
+    chars: Peekable<Chars<'a>>,
+    column: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(input: &'a str) -> Scanner<'a> {
+        Scanner {
+            chars: input.chars().peekable(),
+            column: 1,
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied().filter(|&c| c != '#')
+    }
+
+    fn bump(&mut self) {
+        self.chars.next();
+        self.column += 1;
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+        let mut taken = String::new();
+        while let Some(c) = self.peek().filter(|&c| predicate(c)) {
+            taken.push(c);
+            self.bump();
+        }
+        taken
+    }
+
+    fn error(&self, reason: ParseErrorReason) -> ParseError {
+        self.error_at(self.column, reason)
+    }
+
+    fn error_at(&self, column: usize, reason: ParseErrorReason) -> ParseError {
+        ParseError {
+            line: 1,
+            column,
+            reason,
+        }
+    }
+
+    fn direction(&mut self) -> Result<fn(u64) -> DialDirection, ParseError> {
+        self.skip_whitespace();
+        let column = self.column;
+        match self.peek() {
+            None => Err(self.error(ParseErrorReason::MissingDirection)),
+            Some('L' | 'l') => {
+                self.bump();
+                Ok(DialDirection::Left)
+            }
+            Some('R' | 'r') => {
+                self.bump();
+                Ok(DialDirection::Right)
+            }
+            Some(c) if c.is_alphabetic() => {
+                let word = self.take_while(char::is_alphabetic);
+                match word.to_ascii_uppercase().as_str() {
+                    "CCW" => Ok(DialDirection::Left),
+                    "CW" => Ok(DialDirection::Right),
+                    _ => Err(self.error_at(column, ParseErrorReason::InvalidDirection(word))),
+                }
+            }
+            Some(c) => Err(self.error(ParseErrorReason::InvalidDirection(c.to_string()))),
+        }
+    }
+
+    fn steps(&mut self) -> Result<u64, ParseError> {
+        self.skip_whitespace();
+        if self.peek() == Some('+') {
+            self.bump();
+            self.skip_whitespace();
+        }
+        let column = self.column;
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        match self.peek() {
+            None if digits.is_empty() => return Err(self.error(ParseErrorReason::MissingSteps)),
+            Some(c) if !c.is_whitespace() => {
+                return Err(self.error(ParseErrorReason::InvalidStepsCharacter(c)));
+            }
+            _ => {}
+        }
+        digits
+            .parse()
+            .map_err(|_| self.error_at(column, ParseErrorReason::StepsOutOfRange))
+    }
+
+    fn end(&mut self) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            None => Ok(()),
+            Some(c) => Err(self.error(ParseErrorReason::UnexpectedCharacter(c))),
+        }
+    }
+
+    /// Reads the `3x` of `3xL10`, given the number already read at `column`.
+    fn repeat(&mut self, number: &str, column: usize) -> Result<u64, ParseError> {
+        self.bump();
+        match number.parse::<u64>() {
+            Ok(repeat) if repeat > MAXIMUM_REPEAT => {
+                Err(self.error_at(column, ParseErrorReason::RepeatOutOfRange))
+            }
+            Ok(repeat) if repeat > 0 => Ok(repeat),
+            Err(error) if *error.kind() == IntErrorKind::PosOverflow => {
+                Err(self.error_at(column, ParseErrorReason::RepeatOutOfRange))
+            }
+            _ => Err(self.error_at(column, ParseErrorReason::InvalidRepeat)),
+        }
+    }
+
+    fn statement(&mut self) -> Result<Option<Statement>, ParseError> {
+        self.skip_whitespace();
+        if self.peek().is_none() {
+            return Ok(None);
+        }
+
+        let mut wheel = 1;
+        let mut repeat = 1;
+        if self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            let column = self.column;
+            let number = self.take_while(|c| c.is_ascii_digit());
+            self.skip_whitespace();
+            match self.peek() {
+                Some(':') => {
+                    self.bump();
+                    wheel = match number.parse::<usize>() {
+                        Ok(wheel) if wheel > 0 => wheel,
+                        _ => return Err(self.error_at(column, ParseErrorReason::InvalidWheel)),
+                    };
+                    self.skip_whitespace();
+                    if self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                        let column = self.column;
+                        let number = self.take_while(|c| c.is_ascii_digit());
+                        self.skip_whitespace();
+                        if !matches!(self.peek(), Some('x' | 'X')) {
+                            return Err(self.error_at(column, ParseErrorReason::UnexpectedNumber));
+                        }
+                        repeat = self.repeat(&number, column)?;
+                    }
+                }
+                Some('x' | 'X') => repeat = self.repeat(&number, column)?,
+                _ => return Err(self.error_at(column, ParseErrorReason::UnexpectedNumber)),
+            }
+        }
+
+        let direction = self.direction()?;
+        let steps = self.steps()?;
+        self.end()?;
+        Ok(Some(Statement {
+            wheel_instruction: WheelInstruction {
+                wheel,
+                dial_direction: direction(steps),
+            },
+            repeat,
+        }))
+    }
+}
+
+/// Parses a single instruction such as `L68`, `r+48` or `CCW5`.
+///
+/// The input is treated as line 1; callers that parse a whole file should
+/// use `ParseError::at_line` to report the real line number.
+pub fn parse_instruction(input: &str) -> Result<DialDirection, ParseError> {
+    let mut scanner = Scanner::new(input);
+    let direction = scanner.direction()?;
+    let steps = scanner.steps()?;
+    scanner.end()?;
+    Ok(direction(steps))
+}
+
+/// Parses a whole line such as `2:3xL10 # comment`, following the grammar at
+/// the top of this module. Returns `None` for blank and comment-only lines.
+pub fn parse_statement(input: &str) -> Result<Option<Statement>, ParseError> {
+    Scanner::new(input).statement()
+}
+
+/// Streams instructions from `reader` one line at a time, reusing a single
+/// line buffer so memory use does not grow with the size of the input.
+/// Blank and comment lines are skipped and repeated instructions are yielded
+/// once per repetition; each item carries its 1-based line number.
+pub struct Instructions<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    line_number: usize,
+    pending: Option<(WheelInstruction, u64)>,
+}
+
+pub fn instructions<R: BufRead>(reader: R) -> Instructions<R> {
+    Instructions {
+        reader,
+        buffer: vec![],
+        line_number: 0,
+        pending: None,
+    }
+}
+
+impl<R: BufRead> Iterator for Instructions<R> {
+    type Item = io::Result<(usize, Result<WheelInstruction, ParseError>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((wheel_instruction, remaining)) = self.pending.take() {
+            if remaining > 1 {
+                self.pending = Some((wheel_instruction, remaining - 1));
+            }
+            return Some(Ok((self.line_number, Ok(wheel_instruction))));
+        }
+        loop {
+            self.buffer.clear();
+            match self.reader.read_until(b'\n', &mut self.buffer) {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(error) => return Some(Err(error)),
+            }
+            self.line_number += 1;
+            let line_number = self.line_number;
+            // A line that is not UTF-8 is a bad line like any other, so that
+            // `--lenient` can skip it.
+            let line = match str::from_utf8(&self.buffer) {
+                Ok(line) => line,
+                Err(error) => {
+                    let valid = str::from_utf8(&self.buffer[..error.valid_up_to()])
+                        .expect("prefix is valid UTF-8");
+                    return Some(Ok((
+                        line_number,
+                        Err(ParseError {
+                            line: line_number,
+                            column: valid.chars().count() + 1,
+                            reason: ParseErrorReason::InvalidUtf8,
+                        }),
+                    )));
+                }
+            };
+            match parse_statement(line) {
+                Ok(None) => continue,
+                Ok(Some(Statement {
+                    wheel_instruction,
+                    repeat,
+                })) => {
+                    if repeat > 1 {
+                        self.pending = Some((wheel_instruction, repeat - 1));
+                    }
+                    return Some(Ok((line_number, Ok(wheel_instruction))));
+                }
+                Err(error) => return Some(Ok((line_number, Err(error.at_line(line_number))))),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_instruction_case_1() {
+        let result = parse_instruction("L68");
+        assert!(matches!(result, Ok(DialDirection::Left(68))));
+    }
+
+    #[test]
+    fn test_parse_instruction_case_2() {
+        let result = parse_instruction("R48");
+        assert!(matches!(result, Ok(DialDirection::Right(48))));
+    }
+
+    #[test]
+    fn test_parse_instruction_case_3() {
+        let result = parse_instruction("  R18446744073709551615\r");
+        assert!(matches!(result, Ok(DialDirection::Right(u64::MAX))));
+    }
+
+    #[test]
+    fn test_parse_instruction_case_4() {
+        let result = parse_instruction("L");
+        assert_eq!(
+            result.err(),
+            Some(ParseError {
+                line: 1,
+                column: 2,
+                reason: ParseErrorReason::MissingSteps
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_instruction_case_5() {
+        let result = parse_instruction("é12");
+        assert_eq!(
+            result.err(),
+            Some(ParseError {
+                line: 1,
+                column: 1,
+                reason: ParseErrorReason::InvalidDirection("é".to_string())
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_instruction_case_6() {
+        let result = parse_instruction("R4x8");
+        assert_eq!(
+            result.err(),
+            Some(ParseError {
+                line: 1,
+                column: 3,
+                reason: ParseErrorReason::InvalidStepsCharacter('x')
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_instruction_case_7() {
+        let result = parse_instruction("R18446744073709551616");
+        assert_eq!(
+            result.err(),
+            Some(ParseError {
+                line: 1,
+                column: 2,
+                reason: ParseErrorReason::StepsOutOfRange
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_instruction_case_8() {
+        let result = parse_instruction("");
+        assert_eq!(
+            result.err(),
+            Some(ParseError {
+                line: 1,
+                column: 1,
+                reason: ParseErrorReason::MissingDirection
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_instruction_case_9() {
+        assert_eq!(parse_instruction("L+68"), Ok(DialDirection::Left(68)));
+        assert_eq!(parse_instruction("r48"), Ok(DialDirection::Right(48)));
+        assert_eq!(parse_instruction("cw 5"), Ok(DialDirection::Right(5)));
+        assert_eq!(parse_instruction("CCW5 # back"), Ok(DialDirection::Left(5)));
+    }
+
+    #[test]
+    fn test_parse_instruction_case_10() {
+        let result = parse_instruction(" CWW5");
+        assert_eq!(
+            result.err(),
+            Some(ParseError {
+                line: 1,
+                column: 2,
+                reason: ParseErrorReason::InvalidDirection("CWW".to_string())
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_instruction_case_11() {
+        let result = parse_instruction("R5 6");
+        assert_eq!(
+            result.err(),
+            Some(ParseError {
+                line: 1,
+                column: 4,
+                reason: ParseErrorReason::UnexpectedCharacter('6')
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_error_at_line_case_1() {
+        let error = parse_instruction("L").unwrap_err().at_line(42);
+        assert_eq!(
+            error.to_string(),
+            "line 42, column 2: missing number of steps"
+        );
+    }
+
+    #[test]
+    fn test_parse_statement_case_1() {
+        let result = parse_statement("2:L30");
+        assert_eq!(
+            result,
+            Ok(Some(Statement {
+                wheel_instruction: WheelInstruction {
+                    wheel: 2,
+                    dial_direction: DialDirection::Left(30)
+                },
+                repeat: 1
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_statement_case_2() {
+        let result = parse_statement("R7");
+        assert_eq!(
+            result,
+            Ok(Some(Statement {
+                wheel_instruction: WheelInstruction {
+                    wheel: 1,
+                    dial_direction: DialDirection::Right(7)
+                },
+                repeat: 1
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_statement_case_3() {
+        let result = parse_statement("0:R7");
+        assert_eq!(
+            result.err(),
+            Some(ParseError {
+                line: 1,
+                column: 1,
+                reason: ParseErrorReason::InvalidWheel
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_statement_case_4() {
+        let result = parse_statement("12:Rx");
+        assert_eq!(
+            result.err(),
+            Some(ParseError {
+                line: 1,
+                column: 5,
+                reason: ParseErrorReason::InvalidStepsCharacter('x')
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_statement_case_5() {
+        let result = parse_statement("  2 : 3x L+10   # three small turns");
+        assert_eq!(
+            result,
+            Ok(Some(Statement {
+                wheel_instruction: WheelInstruction {
+                    wheel: 2,
+                    dial_direction: DialDirection::Left(10)
+                },
+                repeat: 3
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_statement_case_6() {
+        assert_eq!(parse_statement("# L68"), Ok(None));
+        assert_eq!(parse_statement(" \t\r\n"), Ok(None));
+    }
+
+    #[test]
+    fn test_parse_statement_case_7() {
+        let result = parse_statement("0xR5");
+        assert_eq!(
+            result.err(),
+            Some(ParseError {
+                line: 1,
+                column: 1,
+                reason: ParseErrorReason::InvalidRepeat
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_statement_case_8() {
+        let result = parse_statement("3 L5");
+        assert_eq!(
+            result.err(),
+            Some(ParseError {
+                line: 1,
+                column: 1,
+                reason: ParseErrorReason::UnexpectedNumber
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_statement_case_9() {
+        let result = parse_statement("2:4L5");
+        assert_eq!(
+            result.err(),
+            Some(ParseError {
+                line: 1,
+                column: 3,
+                reason: ParseErrorReason::UnexpectedNumber
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_statement_case_10() {
+        for input in [
+            "18446744073709551615xL1",
+            "1000001xR1",
+            "2:99999999999999999999xL1",
+        ] {
+            assert_eq!(
+                parse_statement(input).err().map(|error| error.reason),
+                Some(ParseErrorReason::RepeatOutOfRange)
+            );
+        }
+        assert_eq!(
+            parse_statement("1000000xR1").map(|statement| statement.map(|s| s.repeat)),
+            Ok(Some(MAXIMUM_REPEAT))
+        );
+    }
+
+    #[test]
+    fn test_instructions_case_1() {
+        let input = io::Cursor::new("L68\n\nR48\r\nX1\n");
+        let items: Vec<_> = instructions(input).map(Result::unwrap).collect();
+        assert_eq!(items.len(), 3);
+        assert!(matches!(
+            items[0],
+            (
+                1,
+                Ok(WheelInstruction {
+                    wheel: 1,
+                    dial_direction: DialDirection::Left(68)
+                })
+            )
+        ));
+        assert!(matches!(
+            items[1],
+            (
+                3,
+                Ok(WheelInstruction {
+                    wheel: 1,
+                    dial_direction: DialDirection::Right(48)
+                })
+            )
+        ));
+        assert_eq!(
+            items[2],
+            (
+                4,
+                Err(ParseError {
+                    line: 4,
+                    column: 1,
+                    reason: ParseErrorReason::InvalidDirection("X".to_string())
+                })
+            )
+        );
+    }
+
+    #[test]
+    fn test_instructions_case_2() {
+        let input = io::Cursor::new("R5");
+        let items: Vec<_> = instructions(input).map(Result::unwrap).collect();
+        assert!(matches!(
+            items[..],
+            [(
+                1,
+                Ok(WheelInstruction {
+                    wheel: 1,
+                    dial_direction: DialDirection::Right(5)
+                })
+            )]
+        ));
+    }
+    #[test]
+    fn test_instructions_case_3() {
+        let input = io::Cursor::new("# header\n2xR5 # twice\n\nCCW1\n");
+        let items: Vec<_> = instructions(input)
+            .map(|item| {
+                let (line_number, parsed) = item.unwrap();
+                (line_number, parsed.unwrap().dial_direction)
+            })
+            .collect();
+        assert_eq!(
+            items,
+            vec![
+                (2, DialDirection::Right(5)),
+                (2, DialDirection::Right(5)),
+                (4, DialDirection::Left(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_instructions_case_4() {
+        let input = io::Cursor::new(b"L68\n\xc3\xa9\xffR5\nR1".to_vec());
+        let items: Vec<_> = instructions(input).map(|item| item.unwrap()).collect();
+        assert_eq!(items.len(), 3);
+        assert_eq!(
+            items[1],
+            (
+                2,
+                Err(ParseError {
+                    line: 2,
+                    column: 2,
+                    reason: ParseErrorReason::InvalidUtf8
+                })
+            )
+        );
+        assert!(matches!(items[2], (3, Ok(_))));
+    }
+}
@@ -0,0 +1,7 @@
+pub mod batch;
+pub mod dial;
+pub mod instruction;
+pub mod lock;
+pub mod solver;
+pub mod trace;
+pub mod visualize;
@@ -0,0 +1,317 @@
+use crate::dial::{Dial, DialDirection, DialError, DialResult, ZeroCountingPolicy};
+
+/// How turning one wheel affects the others.
+/// With `Odometer`, every time a wheel passes 0 the next wheel turns one
+/// click in the same direction, which may in turn advance the wheel after it.
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum Coupling {
+    Independent,
+    Odometer,
+}
+
+/// A dial instruction aimed at one wheel of a lock; wheels are numbered from 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WheelInstruction {
+    pub wheel: usize,
+    pub dial_direction: DialDirection,
+}
+
+/// One wheel moved by an instruction, either directly or by a carry.
+/// `dial_result` holds both zero counts, whatever the policy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WheelTurn {
+    pub wheel: usize,
+    pub dial_direction: DialDirection,
+    pub position_before: u64,
+    pub dial_result: DialResult,
+}
+
+/// A combination lock made of `number_of_wheels` identical dials.
+pub struct Lock {
+    dial: Dial,
+    number_of_wheels: usize,
+    coupling: Coupling,
+}
+
+impl Lock {
+    pub fn new(dial: Dial, number_of_wheels: usize, coupling: Coupling) -> Result<Lock, DialError> {
+        if number_of_wheels == 0 {
+            return Err(DialError::NoWheels);
+        }
+        Ok(Lock {
+            dial,
+            number_of_wheels,
+            coupling,
+        })
+    }
+
+    pub fn number_of_wheels(&self) -> usize {
+        self.number_of_wheels
+    }
+
+    pub fn check_wheel(&self, wheel: usize) -> Result<(), DialError> {
+        if wheel == 0 || wheel > self.number_of_wheels {
+            return Err(DialError::WheelOutOfRange {
+                wheel,
+                number_of_wheels: self.number_of_wheels,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn initial_results(&self) -> Vec<DialResult> {
+        vec![self.dial.initial_result(); self.number_of_wheels]
+    }
+
+    /// Applies one instruction to the lock and returns every wheel it moves:
+    /// the wheel it names first, then each wheel advanced by a carry.
+    /// Carries between coupled wheels always follow the `Passes` rule, and
+    /// the results keep all zero counts whatever the policy.
+    pub fn turn_wheels(
+        &self,
+        current_wheel_positions: &[DialResult],
+        wheel_instruction: WheelInstruction,
+    ) -> Result<Vec<WheelTurn>, DialError> {
+        let WheelInstruction {
+            wheel,
+            dial_direction,
+        } = wheel_instruction;
+        self.check_wheel(wheel)?;
+
+        let mut wheel_turns = vec![];
+        let mut index = wheel - 1;
+        let mut dial_direction = dial_direction;
+        loop {
+            let position_before = current_wheel_positions[index].dial_position;
+            let dial_result = self.dial.new_dial_position(
+                current_wheel_positions[index],
+                dial_direction,
+                ZeroCountingPolicy::Both,
+            );
+            wheel_turns.push(WheelTurn {
+                wheel: index + 1,
+                dial_direction,
+                position_before,
+                dial_result,
+            });
+
+            let carry = dial_result.number_of_zeros_crossed;
+            if self.coupling == Coupling::Independent
+                || carry == 0
+                || index + 1 == self.number_of_wheels
+            {
+                break;
+            }
+            index += 1;
+            dial_direction = match dial_direction {
+                DialDirection::Left(_) => DialDirection::Left(carry),
+                DialDirection::Right(_) => DialDirection::Right(carry),
+            };
+        }
+        Ok(wheel_turns)
+    }
+
+    /// Applies one instruction to the lock and returns, for every wheel, its
+    /// new position and the zeros it hit during this instruction.
+    /// `policy` only decides which counts are reported.
+    pub fn new_wheel_positions(
+        &self,
+        current_wheel_positions: Vec<DialResult>,
+        wheel_instruction: WheelInstruction,
+        policy: ZeroCountingPolicy,
+    ) -> Result<Vec<DialResult>, DialError> {
+        let wheel_turns = self.turn_wheels(&current_wheel_positions, wheel_instruction)?;
+        Ok(wheel_positions_after(
+            current_wheel_positions,
+            &wheel_turns,
+            policy,
+        ))
+    }
+}
+
+/// The positions after `wheel_turns`, with this instruction's zero counts
+/// for every wheel and only the counts `policy` asks for.
+pub fn wheel_positions_after(
+    current_wheel_positions: Vec<DialResult>,
+    wheel_turns: &[WheelTurn],
+    policy: ZeroCountingPolicy,
+) -> Vec<DialResult> {
+    let mut wheel_positions: Vec<DialResult> = current_wheel_positions
+        .iter()
+        .map(|dial_result| DialResult {
+            dial_position: dial_result.dial_position,
+            number_of_zeros_crossed: 0,
+            number_of_zeros_landed_on: 0,
+        })
+        .collect();
+    for wheel_turn in wheel_turns {
+        wheel_positions[wheel_turn.wheel - 1] = policy.apply(wheel_turn.dial_result);
+    }
+    wheel_positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positions(wheel_positions: &[DialResult]) -> Vec<u64> {
+        wheel_positions.iter().map(|r| r.dial_position).collect()
+    }
+
+    fn crossings(wheel_positions: &[DialResult]) -> Vec<u64> {
+        wheel_positions
+            .iter()
+            .map(|r| r.number_of_zeros_crossed)
+            .collect()
+    }
+
+    #[test]
+    fn test_lock_new_case_1() {
+        let result = Lock::new(Dial::default(), 0, Coupling::Independent);
+        assert!(matches!(result, Err(DialError::NoWheels)));
+    }
+
+    #[test]
+    fn test_new_wheel_positions_case_1() {
+        let lock = Lock::new(Dial::default(), 3, Coupling::Independent).unwrap();
+        let wheel_positions = lock
+            .new_wheel_positions(
+                lock.initial_results(),
+                WheelInstruction {
+                    wheel: 2,
+                    dial_direction: DialDirection::Left(68),
+                },
+                ZeroCountingPolicy::Passes,
+            )
+            .unwrap();
+        assert_eq!(positions(&wheel_positions), vec![50, 82, 50]);
+        assert_eq!(crossings(&wheel_positions), vec![0, 1, 0]);
+    }
+
+    #[test]
+    fn test_new_wheel_positions_case_2() {
+        let lock = Lock::new(Dial::default(), 3, Coupling::Odometer).unwrap();
+        let wheel_positions = lock
+            .new_wheel_positions(
+                lock.initial_results(),
+                WheelInstruction {
+                    wheel: 1,
+                    dial_direction: DialDirection::Right(250),
+                },
+                ZeroCountingPolicy::Passes,
+            )
+            .unwrap();
+        assert_eq!(positions(&wheel_positions), vec![0, 53, 50]);
+        assert_eq!(crossings(&wheel_positions), vec![3, 0, 0]);
+    }
+
+    #[test]
+    fn test_new_wheel_positions_case_3() {
+        let dial = Dial::new(10, 9).unwrap();
+        let lock = Lock::new(dial, 3, Coupling::Odometer).unwrap();
+        let wheel_positions = lock
+            .new_wheel_positions(
+                lock.initial_results(),
+                WheelInstruction {
+                    wheel: 1,
+                    dial_direction: DialDirection::Right(1),
+                },
+                ZeroCountingPolicy::Both,
+            )
+            .unwrap();
+        assert_eq!(positions(&wheel_positions), vec![0, 0, 0]);
+        assert_eq!(crossings(&wheel_positions), vec![1, 1, 1]);
+    }
+
+    #[test]
+    fn test_new_wheel_positions_case_4() {
+        let dial = Dial::new(10, 0).unwrap();
+        let lock = Lock::new(dial, 2, Coupling::Odometer).unwrap();
+        let wheel_positions = lock
+            .new_wheel_positions(
+                lock.initial_results(),
+                WheelInstruction {
+                    wheel: 2,
+                    dial_direction: DialDirection::Left(25),
+                },
+                ZeroCountingPolicy::LandsOn,
+            )
+            .unwrap();
+        assert_eq!(positions(&wheel_positions), vec![0, 5]);
+        assert_eq!(crossings(&wheel_positions), vec![0, 0]);
+    }
+
+    #[test]
+    fn test_new_wheel_positions_case_5() {
+        let lock = Lock::new(Dial::default(), 2, Coupling::Odometer).unwrap();
+        let result = lock.new_wheel_positions(
+            lock.initial_results(),
+            WheelInstruction {
+                wheel: 3,
+                dial_direction: DialDirection::Left(1),
+            },
+            ZeroCountingPolicy::Passes,
+        );
+        assert!(matches!(
+            result,
+            Err(DialError::WheelOutOfRange {
+                wheel: 3,
+                number_of_wheels: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_turn_wheels_case_1() {
+        let dial = Dial::new(10, 9).unwrap();
+        let lock = Lock::new(dial, 3, Coupling::Odometer).unwrap();
+        let wheel_turns = lock
+            .turn_wheels(
+                &lock.initial_results(),
+                WheelInstruction {
+                    wheel: 1,
+                    dial_direction: DialDirection::Right(21),
+                },
+            )
+            .unwrap();
+        let summary: Vec<(usize, DialDirection, u64, u64, u64, u64)> = wheel_turns
+            .iter()
+            .map(|turn| {
+                (
+                    turn.wheel,
+                    turn.dial_direction,
+                    turn.position_before,
+                    turn.dial_result.dial_position,
+                    turn.dial_result.number_of_zeros_crossed,
+                    turn.dial_result.number_of_zeros_landed_on,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (1, DialDirection::Right(21), 9, 0, 3, 1),
+                (2, DialDirection::Right(3), 9, 2, 1, 0),
+                (3, DialDirection::Right(1), 9, 0, 1, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_turn_wheels_case_2() {
+        let lock = Lock::new(Dial::default(), 2, Coupling::Independent).unwrap();
+        let wheel_turns = lock
+            .turn_wheels(
+                &lock.initial_results(),
+                WheelInstruction {
+                    wheel: 2,
+                    dial_direction: DialDirection::Left(50),
+                },
+            )
+            .unwrap();
+        assert_eq!(wheel_turns.len(), 1);
+        assert_eq!(wheel_turns[0].wheel, 2);
+        assert_eq!(wheel_turns[0].dial_result.number_of_zeros_crossed, 1);
+        assert_eq!(wheel_turns[0].dial_result.number_of_zeros_landed_on, 1);
+    }
+}
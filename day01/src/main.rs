@@ -1,413 +1,322 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use day_1::dial::{Dial, ZeroCountingPolicy, ZeroTally};
+use day_1::instruction::{ParseError, instructions};
+use day_1::lock::{Coupling, Lock, WheelInstruction, wheel_positions_after};
+use day_1::solver::{ZeroCrossingConstraint, solve};
+use day_1::trace::{TraceFormat, TraceRecord, TraceWriter};
+use day_1::visualize;
+use std::error::Error;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader, BufWriter};
+use std::process;
 
-const MAXIMUM_DIAL_POSITION: u8 = 99;
+/// At most this many skipped lines are kept for the warnings printed by `--lenient`.
+const MAXIMUM_STORED_WARNINGS: usize = 100;
 
-/// The dial direction is either Left or Right with a number of steps to turn.
-enum DialDirection {
-    Left(u32),
-    Right(u32),
+/// The lines `--lenient` skipped, reported as warnings once the run is over.
+#[derive(Default)]
+struct SkippedLines {
+    warnings: Vec<String>,
+    number_of_skipped_lines: usize,
 }
 
-struct DialResult {
-    dial_position: u8,
-    number_of_zeros_crossed: u32,
-}
-
-fn new_dial_position(
-    current_dial_position: DialResult,
-    dial_direction: DialDirection,
-) -> DialResult {
-    match dial_direction {
-        DialDirection::Left(steps) => dial_left(current_dial_position, steps),
-        DialDirection::Right(steps) => dial_right(current_dial_position, steps),
-    }
-}
-
-fn dial_right(current_dial_position: DialResult, steps: u32) -> DialResult {
-    assert!(current_dial_position.dial_position <= MAXIMUM_DIAL_POSITION);
-
-    let result: u32 = current_dial_position.dial_position as u32 + steps;
-    let number_of_zeros_crossed = result / (MAXIMUM_DIAL_POSITION as u32 + 1);
-
-    DialResult {
-        dial_position: (result.rem_euclid(MAXIMUM_DIAL_POSITION as u32 + 1)) as u8,
-        number_of_zeros_crossed: number_of_zeros_crossed,
-    }
-}
-
-/// Calculates the new dial position when dialling left
-fn dial_left(current_dial_position: DialResult, steps: u32) -> DialResult {
-    assert!(current_dial_position.dial_position <= MAXIMUM_DIAL_POSITION);
-
-    let dial_position: i32 = current_dial_position.dial_position as i32 - steps as i32;
-    let mut number_of_zeros_crossed: u32;
-
-    if dial_position > 0 {
-        number_of_zeros_crossed = 0
-    } else {
-        let abs_dial_position = dial_position.abs() as u32;
-        number_of_zeros_crossed = abs_dial_position / (MAXIMUM_DIAL_POSITION as u32 + 1) + 1;
-        if current_dial_position.dial_position == 0 {
-            number_of_zeros_crossed -= 1;
+impl SkippedLines {
+    fn push(&mut self, warning: String) {
+        self.number_of_skipped_lines += 1;
+        if self.warnings.len() < MAXIMUM_STORED_WARNINGS {
+            self.warnings.push(warning);
         }
     }
 
-    DialResult {
-        dial_position: (dial_position.rem_euclid(MAXIMUM_DIAL_POSITION as i32 + 1)) as u8,
-        number_of_zeros_crossed: number_of_zeros_crossed,
+    fn print(&self) {
+        for warning in &self.warnings {
+            eprintln!("warning: skipped {}", warning);
+        }
+        if self.number_of_skipped_lines > self.warnings.len() {
+            eprintln!(
+                "warning: skipped {} more lines",
+                self.number_of_skipped_lines - self.warnings.len()
+            );
+        }
     }
 }
 
-fn read_lines(path: &str) -> std::io::Result<Vec<String>> {
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
-    reader.lines().collect()
+/// Opens the input file, or standard input when no file or `-` is given.
+fn open_input(path: Option<&str>) -> io::Result<Box<dyn BufRead>> {
+    match path {
+        None | Some("-") => Ok(Box::new(io::stdin().lock())),
+        Some(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+    }
 }
 
 #[derive(Parser, Debug)]
 struct Args {
-    #[arg(short, long, default_value = "input.txt")]
-    input_file: String,
-}
+    /// File with one instruction per line; reads standard input when omitted or `-`.
+    #[arg(short, long)]
+    input_file: Option<String>,
 
-fn main() {
-    let args = Args::parse();
-    let lines = read_lines(&args.input_file).unwrap();
-    let mut current_dial_position = DialResult {
-        dial_position: 50,
-        number_of_zeros_crossed: 0,
-    };
-    let mut number_of_zeros = 0;
-    for line in lines {
-        if line == "" {
-            continue;
-        }
-        let direction_char: &str = &line[0..1];
-        let steps_string: &str = &line[1..line.len()];
-        let steps: u32 = steps_string.parse::<u32>().unwrap();
-        println!("direction_char {} steps {}", direction_char, steps);
-        let dial_direction = match direction_char {
-            "R" => DialDirection::Right(steps),
-            "L" => DialDirection::Left(steps),
-            _ => panic!("Invalid dial direction {}", direction_char),
-        };
-        current_dial_position = new_dial_position(current_dial_position, dial_direction);
-        println!(
-            "New dial position = {}/{}",
-            current_dial_position.dial_position, current_dial_position.number_of_zeros_crossed
-        );
-        number_of_zeros += current_dial_position.number_of_zeros_crossed;
-        // if current_dial_position.dial_position == 0 {
-        // number_of_zeros += 1;
-        // }
-    }
-    println!("Number of Zeros {}", number_of_zeros);
-}
+    /// Number of positions on the dial, numbered from 0 to dial-size - 1.
+    #[arg(long, global = true, default_value_t = 100)]
+    dial_size: u64,
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Position the dial points at before the first instruction.
+    #[arg(long, global = true, default_value_t = 50)]
+    start: u64,
 
-    #[test]
-    fn test_dial_left_case_dial_before_zero_case_1() {
-        let current_dial_position = DialResult {
-            dial_position: 50,
-            number_of_zeros_crossed: 0,
-        };
-        let steps = 1;
-        let dial_result = dial_left(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 49);
-        assert_eq!(dial_result.number_of_zeros_crossed, 0);
-    }
+    /// Skip lines that cannot be parsed and list them as warnings at the end.
+    #[arg(long)]
+    lenient: bool,
 
-    #[test]
-    fn test_dial_left_case_dial_past_zero_case_1() {
-        let current_dial_position = DialResult {
-            dial_position: 50,
-            number_of_zeros_crossed: 0,
-        };
-        let steps = 51;
-        let dial_result = dial_left(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 99);
-        assert_eq!(dial_result.number_of_zeros_crossed, 1);
-    }
+    /// Which zero events to count: landing on 0, passing 0, or both.
+    #[arg(long, value_enum, default_value_t = ZeroCountingPolicy::Passes)]
+    policy: ZeroCountingPolicy,
 
-    #[test]
-    fn test_dial_left_case_dial_at_zero_case_1() {
-        let current_dial_position = DialResult {
-            dial_position: 50,
-            number_of_zeros_crossed: 0,
-        };
-        let steps = 50;
-        let dial_result = dial_left(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 0);
-        assert_eq!(dial_result.number_of_zeros_crossed, 1);
-    }
+    /// Trace every instruction on stderr; repeat (-vv) for more detail.
+    #[arg(short, long, global = true, action = clap::ArgAction::Count, conflicts_with = "quiet")]
+    verbose: u8,
 
-    #[test]
-    fn test_dial_left_case_dial_past_zero_case_2() {
-        let current_dial_position = DialResult {
-            dial_position: 50,
-            number_of_zeros_crossed: 0,
-        };
-        let steps = 52;
-        let dial_result = dial_left(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 98);
-        assert_eq!(dial_result.number_of_zeros_crossed, 1);
-    }
-
-    #[test]
-    fn test_dial_left_case_dial_past_zero_full_cycle_case_1() {
-        let current_dial_position = DialResult {
-            dial_position: 50,
-            number_of_zeros_crossed: 0,
-        };
-        let steps = 50 + 50;
-        let dial_result = dial_left(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 50);
-        assert_eq!(dial_result.number_of_zeros_crossed, 1);
-    }
+    /// Print only the final count(s), without labels or warnings.
+    #[arg(short, long, global = true)]
+    quiet: bool,
 
-    #[test]
-    fn test_dial_left_case_dial_past_zero_full_cycle_case_2() {
-        let current_dial_position = DialResult {
-            dial_position: 50,
-            number_of_zeros_crossed: 0,
-        };
-        let steps = 50 + 50 + 2;
-        let dial_result = dial_left(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 48);
-        assert_eq!(dial_result.number_of_zeros_crossed, 1);
-    }
+    /// Format of the step-by-step trace written to --trace-out.
+    #[arg(long, value_enum, default_value_t = TraceFormat::Csv)]
+    trace_format: TraceFormat,
 
-    #[test]
-    fn test_dial_left_case_dial_past_zero_full_cycle_case_3() {
-        let current_dial_position = DialResult {
-            dial_position: 50,
-            number_of_zeros_crossed: 0,
-        };
-        let steps = 10 * 50 + 2;
-        let dial_result = dial_left(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 48);
-        assert_eq!(dial_result.number_of_zeros_crossed, 5);
-    }
+    /// Write one record per instruction to this file.
+    #[arg(long)]
+    trace_out: Option<String>,
 
-    #[test]
-    fn test_dial_left_case_dial_past_zero_full_cycle_case_4() {
-        let current_dial_position = DialResult {
-            dial_position: 50,
-            number_of_zeros_crossed: 0,
-        };
-        let steps: u32 = 6 * (MAXIMUM_DIAL_POSITION as u32 + 1) + 2;
-        let dial_result = dial_left(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 48);
-        assert_eq!(dial_result.number_of_zeros_crossed, 6);
-    }
+    /// Number of wheels in the lock; prefix an instruction with `N:` to turn wheel N.
+    #[arg(long, default_value_t = 1)]
+    wheels: usize,
 
-    #[test]
-    fn test_dial_left_case_dial_past_zero_full_cycle_case_5() {
-        let current_dial_position = DialResult {
-            dial_position: 50,
-            number_of_zeros_crossed: 0,
-        };
-        let steps: u32 = 6 * (MAXIMUM_DIAL_POSITION as u32 + 1) - 2;
-        let dial_result = dial_left(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 52);
-        assert_eq!(dial_result.number_of_zeros_crossed, 6);
-    }
+    /// Whether a wheel passing 0 advances the next wheel like an odometer.
+    #[arg(long, value_enum, default_value_t = Coupling::Independent)]
+    coupling: Coupling,
 
-    #[test]
-    fn test_dial_left_case_6() {
-        let current_dial_position = DialResult {
-            dial_position: 50,
-            number_of_zeros_crossed: 0,
-        };
-        let steps = 68;
-        let dial_result = dial_left(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 82);
-        assert_eq!(dial_result.number_of_zeros_crossed, 1);
-    }
+    /// Animate the dial in the terminal: space pauses, n single-steps, +/- change the speed, q quits.
+    #[arg(long, conflicts_with_all = ["wheels", "coupling", "trace_out"])]
+    visualize: bool,
 
-    #[test]
-    fn test_dial_left_case_7() {
-        let current_dial_position = DialResult {
-            dial_position: 82,
-            number_of_zeros_crossed: 0,
-        };
-        let steps = 30;
-        let dial_result = dial_left(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 52);
-        assert_eq!(dial_result.number_of_zeros_crossed, 0);
-    }
-
-    #[test]
-    fn test_dial_left_case_8() {
-        let current_dial_position = DialResult {
-            dial_position: 50,
-            number_of_zeros_crossed: 0,
-        };
-        let steps = 68;
-        let dial_result = dial_left(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 82);
-        assert_eq!(dial_result.number_of_zeros_crossed, 1);
-    }
-
-    #[test]
-    fn test_dial_left_case_9() {
-        let current_dial_position = DialResult {
-            dial_position: 82,
-            number_of_zeros_crossed: 0,
-        };
-        let steps = 30;
-        let dial_result = dial_left(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 52);
-        assert_eq!(dial_result.number_of_zeros_crossed, 0);
-    }
-
-    #[test]
-    fn test_dial_left_case_10() {
-        let current_dial_position = DialResult {
-            dial_position: 0,
-            number_of_zeros_crossed: 0,
-        };
-        let steps = 5;
-        let dial_result = dial_left(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 95);
-        assert_eq!(dial_result.number_of_zeros_crossed, 0);
-    }
+    #[command(subcommand)]
+    command: Option<Command>,
+}
 
-    //---------------- Right movement -------------------------------
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print the shortest instructions that turn the dial from --start to --target.
+    Solve {
+        /// Position the dial should point at after the instructions.
+        #[arg(long)]
+        target: u64,
+
+        /// Require exactly this many zero crossings.
+        #[arg(long, group = "zero_crossings")]
+        exactly: Option<u64>,
+
+        /// Allow at most this many zero crossings.
+        #[arg(long, group = "zero_crossings")]
+        at_most: Option<u64>,
+
+        /// Require at least this many zero crossings.
+        #[arg(long, group = "zero_crossings")]
+        at_least: Option<u64>,
+    },
+}
 
-    #[test]
-    fn test_dial_right_case_1() {
-        let current_dial_position = DialResult {
-            dial_position: 50,
-            number_of_zeros_crossed: 0,
-        };
-        let steps = 1;
-        let dial_result = dial_right(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 51);
-        assert_eq!(dial_result.number_of_zeros_crossed, 0);
+fn main() {
+    let args = Args::parse();
+    if let Err(error) = run(&args) {
+        eprintln!("{}", error);
+        process::exit(1);
     }
+}
 
-    #[test]
-    fn test_dial_right_case_2() {
-        let current_dial_position = DialResult {
-            dial_position: 50,
-            number_of_zeros_crossed: 0,
-        };
-        let steps = 49;
-        let dial_result = dial_right(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 99);
-        assert_eq!(dial_result.number_of_zeros_crossed, 0);
+fn run(args: &Args) -> Result<(), Box<dyn Error>> {
+    let dial = Dial::new(args.dial_size, args.start)?;
+    match args.command {
+        Some(Command::Solve {
+            target,
+            exactly,
+            at_most,
+            at_least,
+        }) => {
+            let constraint = match (exactly, at_most, at_least) {
+                (Some(k), _, _) => ZeroCrossingConstraint::Exactly(k),
+                (_, Some(k), _) => ZeroCrossingConstraint::AtMost(k),
+                (_, _, Some(k)) => ZeroCrossingConstraint::AtLeast(k),
+                _ => ZeroCrossingConstraint::Any,
+            };
+            let solution = solve(&dial, target, constraint)?;
+            for instruction in &solution.instructions {
+                println!("{}", instruction);
+            }
+            if args.verbose >= 1 {
+                eprintln!(
+                    "{} instruction(s), zeros crossed {}",
+                    solution.instructions.len(),
+                    solution.number_of_zeros_crossed
+                );
+            }
+            Ok(())
+        }
+        None if args.visualize => animate(args, &dial),
+        None => simulate(args, &dial),
     }
+}
 
-    #[test]
-    fn test_dial_right_case_3() {
-        let current_dial_position = DialResult {
-            dial_position: 50,
-            number_of_zeros_crossed: 0,
-        };
-        let steps = 50;
-        let dial_result = dial_right(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 0);
-        assert_eq!(dial_result.number_of_zeros_crossed, 1);
+/// Plays the instructions from the input on the terminal dial, then prints the
+/// zero counts of the instructions shown.
+fn animate(args: &Args, dial: &Dial) -> Result<(), Box<dyn Error>> {
+    let reader = open_input(args.input_file.as_deref())?;
+    let lock = Lock::new(*dial, 1, Coupling::Independent)?;
+    let mut skipped_lines = SkippedLines::default();
+    let dial_directions = instructions(reader).filter_map(|item| match item {
+        Err(error) => Some(Err(error.into())),
+        Ok((line_number, parsed)) => match checked_instruction(&lock, line_number, parsed) {
+            Ok(wheel_instruction) => Some(Ok((line_number, wheel_instruction.dial_direction))),
+            Err(error) if args.lenient => {
+                skipped_lines.push(error);
+                None
+            }
+            Err(error) => Some(Err(error.into())),
+        },
+    });
+    let zero_tally = visualize::run(dial, args.policy, dial_directions)?;
+    print_zero_tally(args, "", &zero_tally);
+    if !args.quiet {
+        skipped_lines.print();
     }
+    Ok(())
+}
 
-    #[test]
-    fn test_dial_right_case_4() {
-        let current_dial_position = DialResult {
-            dial_position: 50,
-            number_of_zeros_crossed: 0,
-        };
-        let steps = 51;
-        let dial_result = dial_right(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 1);
-        assert_eq!(dial_result.number_of_zeros_crossed, 1);
-    }
+/// Checks that a parsed line turns a wheel the lock has, so that a missing
+/// wheel is reported with its line number and skipped by `--lenient` like
+/// any other bad line.
+fn checked_instruction(
+    lock: &Lock,
+    line_number: usize,
+    parsed: Result<WheelInstruction, ParseError>,
+) -> Result<WheelInstruction, String> {
+    let wheel_instruction = parsed.map_err(|error| error.to_string())?;
+    lock.check_wheel(wheel_instruction.wheel)
+        .map_err(|error| format!("line {}: {}", line_number, error))?;
+    Ok(wheel_instruction)
+}
 
-    #[test]
-    fn test_dial_right_case_5() {
-        let current_dial_position = DialResult {
-            dial_position: 50,
-            number_of_zeros_crossed: 0,
-        };
-        let steps = 151;
-        let dial_result = dial_right(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 1);
-        assert_eq!(dial_result.number_of_zeros_crossed, 2);
+/// Runs the instructions from the input through the lock and prints the zero counts.
+fn simulate(args: &Args, dial: &Dial) -> Result<(), Box<dyn Error>> {
+    let lock = Lock::new(*dial, args.wheels, args.coupling)?;
+    let reader = open_input(args.input_file.as_deref())?;
+    let mut wheel_positions = lock.initial_results();
+    let mut zero_tallies: Vec<ZeroTally> = (0..lock.number_of_wheels())
+        .map(|_| ZeroTally::default())
+        .collect();
+    let mut skipped_lines = SkippedLines::default();
+    let mut trace_writer = match &args.trace_out {
+        Some(path) => Some(TraceWriter::new(
+            args.trace_format,
+            BufWriter::new(File::create(path)?),
+        )?),
+        None => None,
+    };
+    if args.verbose >= 2 {
+        eprintln!(
+            "dial size {}, start position {}, policy {:?}, wheels {}, coupling {:?}",
+            args.dial_size, args.start, args.policy, args.wheels, args.coupling
+        );
     }
-
-    #[test]
-    fn test_dial_right_case_6() {
-        let current_dial_position = DialResult {
-            dial_position: 50,
-            number_of_zeros_crossed: 0,
+    for item in instructions(reader) {
+        let (line_number, parsed) = item?;
+        let checked = checked_instruction(&lock, line_number, parsed);
+        let wheel_instruction = match checked {
+            Ok(wheel_instruction) => wheel_instruction,
+            Err(error) if args.lenient => {
+                skipped_lines.push(error);
+                continue;
+            }
+            Err(error) => return Err(error.into()),
         };
-        let steps = 251;
-        let dial_result = dial_right(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 1);
-        assert_eq!(dial_result.number_of_zeros_crossed, 3);
+        let wheel = wheel_instruction.wheel;
+        let wheel_turns = lock.turn_wheels(&wheel_positions, wheel_instruction)?;
+        for wheel_turn in &wheel_turns {
+            if let Some(trace_writer) = &mut trace_writer {
+                trace_writer.write(&TraceRecord::new(
+                    line_number,
+                    wheel_turn.wheel,
+                    wheel_turn.dial_direction,
+                    wheel_turn.position_before,
+                    &wheel_turn.dial_result,
+                ))?;
+            }
+            if args.verbose >= 2 {
+                eprintln!(
+                    "line {}: wheel {} {:?} from {} to {}, zeros crossed {}, landed on {}",
+                    line_number,
+                    wheel_turn.wheel,
+                    wheel_turn.dial_direction,
+                    wheel_turn.position_before,
+                    wheel_turn.dial_result.dial_position,
+                    wheel_turn.dial_result.number_of_zeros_crossed,
+                    wheel_turn.dial_result.number_of_zeros_landed_on
+                );
+            }
+        }
+        wheel_positions = wheel_positions_after(wheel_positions, &wheel_turns, args.policy);
+        if args.verbose == 1 {
+            let current_dial_position = &wheel_positions[wheel - 1];
+            eprintln!(
+                "New dial position = {}/{}",
+                current_dial_position.dial_position, current_dial_position.number_of_zeros_crossed
+            );
+        }
+        for (zero_tally, dial_result) in zero_tallies.iter_mut().zip(&wheel_positions) {
+            zero_tally.add(dial_result)?;
+        }
     }
-    #[test]
-    fn test_dial_right_case_7() {
-        let current_dial_position = DialResult {
-            dial_position: 52,
-            number_of_zeros_crossed: 0,
-        };
-        let steps = 48;
-        let dial_result = dial_right(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 0);
-        assert_eq!(dial_result.number_of_zeros_crossed, 1);
+    if let Some(trace_writer) = trace_writer {
+        trace_writer.finish()?;
     }
-
-    #[test]
-    fn test_dial_right_case_8() {
-        let current_dial_position = DialResult {
-            dial_position: 50,
-            number_of_zeros_crossed: 0,
+    for (index, zero_tally) in zero_tallies.iter().enumerate() {
+        let wheel_label = if zero_tallies.len() > 1 {
+            format!("Wheel {} ", index + 1)
+        } else {
+            String::new()
         };
-        let steps = 258;
-        let dial_result = dial_right(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 8);
-        assert_eq!(dial_result.number_of_zeros_crossed, 3);
+        print_zero_tally(args, &wheel_label, zero_tally);
     }
-
-    #[test]
-    fn test_dial_right_case_9() {
-        let current_dial_position = DialResult {
-            dial_position: 10,
-            number_of_zeros_crossed: 0,
-        };
-        let steps = 90;
-        let dial_result = dial_right(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 0);
-        assert_eq!(dial_result.number_of_zeros_crossed, 1);
+    if !args.quiet {
+        skipped_lines.print();
     }
+    Ok(())
+}
 
-    #[test]
-    fn test_dial_right_case_10() {
-        let current_dial_position = DialResult {
-            dial_position: 10,
-            number_of_zeros_crossed: 0,
-        };
-        let steps = 91;
-        let dial_result = dial_right(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 1);
-        assert_eq!(dial_result.number_of_zeros_crossed, 1);
+fn print_zero_tally(args: &Args, wheel_label: &str, zero_tally: &ZeroTally) {
+    if args.quiet {
+        match args.policy {
+            ZeroCountingPolicy::LandsOn => println!("{}", zero_tally.landed_on),
+            ZeroCountingPolicy::Passes => println!("{}", zero_tally.crossed),
+            ZeroCountingPolicy::Both => println!("{} {}", zero_tally.landed_on, zero_tally.crossed),
+        }
+        return;
     }
-
-    #[test]
-    fn test_dial_right_case_11() {
-        let current_dial_position = DialResult {
-            dial_position: 52,
-            number_of_zeros_crossed: 0,
-        };
-        let steps = 48;
-        let dial_result = dial_right(current_dial_position, steps);
-        assert_eq!(dial_result.dial_position, 0);
-        assert_eq!(dial_result.number_of_zeros_crossed, 1);
+    match args.policy {
+        ZeroCountingPolicy::LandsOn => {
+            println!("{}Number of Zeros {}", wheel_label, zero_tally.landed_on)
+        }
+        ZeroCountingPolicy::Passes => {
+            println!("{}Number of Zeros {}", wheel_label, zero_tally.crossed)
+        }
+        ZeroCountingPolicy::Both => {
+            println!(
+                "{}Number of Zeros landed on {}",
+                wheel_label, zero_tally.landed_on
+            );
+            println!(
+                "{}Number of Zeros passed {}",
+                wheel_label, zero_tally.crossed
+            );
+        }
     }
 }
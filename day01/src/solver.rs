@@ -0,0 +1,233 @@
+//! Inverse of the dial simulation: given where the dial starts and where it
+//! should end up, find the instructions that get it there.
+//!
+//! A single instruction can reach any target, and every extra full turn adds
+//! exactly one zero crossing, so the shortest sequence is always empty or a
+//! single instruction. Among those, the solver picks the one with the fewest
+//! clicks that satisfies the zero crossing constraint. Zero crossings are
+//! counted with the `Passes` rule, as in `DialResult::number_of_zeros_crossed`.
+
+use crate::dial::{Dial, DialDirection, ZeroCountingPolicy};
+use std::fmt;
+
+/// How many zero crossings an acceptable solution may have.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ZeroCrossingConstraint {
+    Any,
+    Exactly(u64),
+    AtMost(u64),
+    AtLeast(u64),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SolveError {
+    TargetOutOfRange { target: u64, size: u64 },
+    Unreachable,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::TargetOutOfRange { target, size } => write!(
+                f,
+                "target position {} should be less than the dial size {}",
+                target, size
+            ),
+            SolveError::Unreachable => {
+                write!(
+                    f,
+                    "no instructions reach the target with that many zero crossings"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+#[derive(Debug, PartialEq)]
+pub struct Solution {
+    pub instructions: Vec<DialDirection>,
+    pub number_of_zeros_crossed: u64,
+}
+
+/// Finds the shortest instruction list that turns `dial` from its start
+/// position to `target` while meeting `constraint`.
+pub fn solve(
+    dial: &Dial,
+    target: u64,
+    constraint: ZeroCrossingConstraint,
+) -> Result<Solution, SolveError> {
+    let size = dial.size();
+    if target >= size {
+        return Err(SolveError::TargetOutOfRange { target, size });
+    }
+    let start = dial.start();
+
+    // Clicks needed to reach the target without any extra full turn.
+    let (right_steps, left_steps) = if target >= start {
+        (target - start, (size - (target - start)) % size)
+    } else {
+        ((size - (start - target)) % size, start - target)
+    };
+
+    let right = candidate(dial, DialDirection::Right, right_steps, constraint);
+    let left = candidate(dial, DialDirection::Left, left_steps, constraint);
+
+    let best = match (right, left) {
+        (Some(right), Some(left)) if steps_of(&left) < steps_of(&right) => left,
+        (Some(right), _) => right,
+        (None, Some(left)) => left,
+        (None, None) => return Err(SolveError::Unreachable),
+    };
+
+    let number_of_zeros_crossed = best.number_of_zeros_crossed;
+    let instructions = match best.instruction {
+        DialDirection::Left(0) | DialDirection::Right(0) => vec![],
+        instruction => vec![instruction],
+    };
+    Ok(Solution {
+        instructions,
+        number_of_zeros_crossed,
+    })
+}
+
+struct Candidate {
+    instruction: DialDirection,
+    number_of_zeros_crossed: u64,
+}
+
+fn steps_of(candidate: &Candidate) -> u64 {
+    match candidate.instruction {
+        DialDirection::Left(steps) | DialDirection::Right(steps) => steps,
+    }
+}
+
+/// The cheapest instruction in one direction that lands on the target and
+/// meets `constraint`, or `None` if no number of extra turns can meet it.
+fn candidate(
+    dial: &Dial,
+    direction: fn(u64) -> DialDirection,
+    base_steps: u64,
+    constraint: ZeroCrossingConstraint,
+) -> Option<Candidate> {
+    let base_crossings = if base_steps == 0 {
+        0
+    } else {
+        dial.new_dial_position(
+            dial.initial_result(),
+            direction(base_steps),
+            ZeroCountingPolicy::Passes,
+        )
+        .number_of_zeros_crossed
+    };
+
+    let extra_turns = match constraint {
+        ZeroCrossingConstraint::Any => 0,
+        ZeroCrossingConstraint::Exactly(k) if k >= base_crossings => k - base_crossings,
+        ZeroCrossingConstraint::AtMost(k) if k >= base_crossings => 0,
+        ZeroCrossingConstraint::AtLeast(k) => k.saturating_sub(base_crossings),
+        _ => return None,
+    };
+
+    let steps = base_steps as u128 + extra_turns as u128 * dial.size() as u128;
+    let steps = u64::try_from(steps).ok()?;
+
+    let dial_result = dial.new_dial_position(
+        dial.initial_result(),
+        direction(steps),
+        ZeroCountingPolicy::Passes,
+    );
+    Some(Candidate {
+        instruction: direction(steps),
+        number_of_zeros_crossed: dial_result.number_of_zeros_crossed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_case_1() {
+        let dial = Dial::default();
+        let solution = solve(&dial, 60, ZeroCrossingConstraint::Any).unwrap();
+        assert_eq!(solution.instructions, vec![DialDirection::Right(10)]);
+        assert_eq!(solution.number_of_zeros_crossed, 0);
+    }
+
+    #[test]
+    fn test_solve_case_2() {
+        let dial = Dial::default();
+        let solution = solve(&dial, 10, ZeroCrossingConstraint::Any).unwrap();
+        assert_eq!(solution.instructions, vec![DialDirection::Left(40)]);
+        assert_eq!(solution.number_of_zeros_crossed, 0);
+    }
+
+    #[test]
+    fn test_solve_case_3() {
+        let dial = Dial::default();
+        let solution = solve(&dial, 50, ZeroCrossingConstraint::Any).unwrap();
+        assert_eq!(solution.instructions, vec![]);
+        assert_eq!(solution.number_of_zeros_crossed, 0);
+    }
+
+    #[test]
+    fn test_solve_case_4() {
+        let dial = Dial::default();
+        let solution = solve(&dial, 60, ZeroCrossingConstraint::Exactly(2)).unwrap();
+        assert_eq!(solution.instructions, vec![DialDirection::Left(190)]);
+        assert_eq!(solution.number_of_zeros_crossed, 2);
+    }
+
+    #[test]
+    fn test_solve_case_5() {
+        let dial = Dial::default();
+        let result = solve(&dial, 0, ZeroCrossingConstraint::AtMost(0));
+        assert_eq!(result, Err(SolveError::Unreachable));
+    }
+
+    #[test]
+    fn test_solve_case_6() {
+        let dial = Dial::default();
+        let solution = solve(&dial, 0, ZeroCrossingConstraint::AtLeast(3)).unwrap();
+        assert_eq!(solution.instructions, vec![DialDirection::Right(250)]);
+        assert_eq!(solution.number_of_zeros_crossed, 3);
+    }
+
+    #[test]
+    fn test_solve_case_7() {
+        let dial = Dial::new(3600, 0).unwrap();
+        let solution = solve(&dial, 0, ZeroCrossingConstraint::Exactly(1)).unwrap();
+        assert_eq!(solution.instructions, vec![DialDirection::Right(3600)]);
+        assert_eq!(solution.number_of_zeros_crossed, 1);
+    }
+
+    #[test]
+    fn test_solve_case_8() {
+        let dial = Dial::default();
+        let result = solve(&dial, 100, ZeroCrossingConstraint::Any);
+        assert_eq!(
+            result,
+            Err(SolveError::TargetOutOfRange {
+                target: 100,
+                size: 100
+            })
+        );
+    }
+
+    #[test]
+    fn test_solve_case_9() {
+        let dial = Dial::new(u64::MAX, u64::MAX - 1).unwrap();
+        let solution = solve(&dial, 1, ZeroCrossingConstraint::Any).unwrap();
+        assert_eq!(solution.instructions, vec![DialDirection::Right(2)]);
+        assert_eq!(solution.number_of_zeros_crossed, 1);
+    }
+
+    #[test]
+    fn test_solve_case_10() {
+        let dial = Dial::new(u64::MAX, 1).unwrap();
+        let result = solve(&dial, 2, ZeroCrossingConstraint::Exactly(2));
+        assert_eq!(result, Err(SolveError::Unreachable));
+    }
+}
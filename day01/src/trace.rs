@@ -0,0 +1,183 @@
+use crate::dial::{DialDirection, DialResult};
+use serde::Serialize;
+use std::io::{self, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum TraceFormat {
+    Csv,
+    Json,
+}
+
+/// One step of the dial simulation, as exported by `--trace-out`.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct TraceRecord {
+    pub line: usize,
+    pub wheel: usize,
+    pub direction: char,
+    pub steps: u64,
+    pub position_before: u64,
+    pub position_after: u64,
+    pub zeros_crossed: u64,
+    pub zeros_landed_on: u64,
+}
+
+impl TraceRecord {
+    pub fn new(
+        line: usize,
+        wheel: usize,
+        dial_direction: DialDirection,
+        position_before: u64,
+        dial_result: &DialResult,
+    ) -> TraceRecord {
+        let (direction, steps) = match dial_direction {
+            DialDirection::Left(steps) => ('L', steps),
+            DialDirection::Right(steps) => ('R', steps),
+        };
+        TraceRecord {
+            line,
+            wheel,
+            direction,
+            steps,
+            position_before,
+            position_after: dial_result.dial_position,
+            zeros_crossed: dial_result.number_of_zeros_crossed,
+            zeros_landed_on: dial_result.number_of_zeros_landed_on,
+        }
+    }
+}
+
+/// Writes trace records one at a time, so the trace never has to be held in memory.
+/// JSON output is a single array; call `finish` to close it.
+pub struct TraceWriter<W: Write> {
+    format: TraceFormat,
+    out: W,
+    records_written: usize,
+}
+
+impl<W: Write> TraceWriter<W> {
+    pub fn new(format: TraceFormat, mut out: W) -> io::Result<TraceWriter<W>> {
+        match format {
+            TraceFormat::Csv => writeln!(
+                out,
+                "line,wheel,direction,steps,position_before,position_after,zeros_crossed,zeros_landed_on"
+            )?,
+            TraceFormat::Json => write!(out, "[")?,
+        }
+        Ok(TraceWriter {
+            format,
+            out,
+            records_written: 0,
+        })
+    }
+
+    pub fn write(&mut self, record: &TraceRecord) -> io::Result<()> {
+        match self.format {
+            TraceFormat::Csv => writeln!(
+                self.out,
+                "{},{},{},{},{},{},{},{}",
+                record.line,
+                record.wheel,
+                record.direction,
+                record.steps,
+                record.position_before,
+                record.position_after,
+                record.zeros_crossed,
+                record.zeros_landed_on
+            )?,
+            TraceFormat::Json => {
+                if self.records_written > 0 {
+                    write!(self.out, ",")?;
+                }
+                writeln!(self.out)?;
+                serde_json::to_writer(&mut self.out, record)?;
+            }
+        }
+        self.records_written += 1;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> io::Result<W> {
+        if self.format == TraceFormat::Json {
+            writeln!(self.out, "\n]")?;
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_records() -> Vec<TraceRecord> {
+        vec![
+            TraceRecord {
+                line: 1,
+                wheel: 1,
+                direction: 'L',
+                steps: 68,
+                position_before: 50,
+                position_after: 82,
+                zeros_crossed: 1,
+                zeros_landed_on: 0,
+            },
+            TraceRecord {
+                line: 3,
+                wheel: 1,
+                direction: 'R',
+                steps: 48,
+                position_before: 52,
+                position_after: 0,
+                zeros_crossed: 1,
+                zeros_landed_on: 1,
+            },
+        ]
+    }
+
+    fn write_all(format: TraceFormat, records: &[TraceRecord]) -> String {
+        let mut trace_writer = TraceWriter::new(format, vec![]).unwrap();
+        for record in records {
+            trace_writer.write(record).unwrap();
+        }
+        String::from_utf8(trace_writer.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_trace_record_new_case_1() {
+        let dial_result = DialResult {
+            dial_position: 82,
+            number_of_zeros_crossed: 1,
+            number_of_zeros_landed_on: 0,
+        };
+        let record = TraceRecord::new(1, 1, DialDirection::Left(68), 50, &dial_result);
+        assert_eq!(record, sample_records().remove(0));
+    }
+
+    #[test]
+    fn test_trace_writer_csv_case_1() {
+        let output = write_all(TraceFormat::Csv, &sample_records());
+        assert_eq!(
+            output,
+            "line,wheel,direction,steps,position_before,position_after,zeros_crossed,zeros_landed_on\n\
+             1,1,L,68,50,82,1,0\n\
+             3,1,R,48,52,0,1,1\n"
+        );
+    }
+
+    #[test]
+    fn test_trace_writer_json_case_1() {
+        let output = write_all(TraceFormat::Json, &sample_records());
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed.as_array().unwrap().len(), 2);
+        assert_eq!(parsed[1]["direction"], "R");
+        assert_eq!(parsed[1]["position_after"], 0);
+        assert_eq!(parsed[0]["zeros_crossed"], 1);
+    }
+
+    #[test]
+    fn test_trace_writer_json_case_2() {
+        let output = write_all(TraceFormat::Json, &[]);
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed.as_array().unwrap().len(), 0);
+    }
+}
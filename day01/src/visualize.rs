@@ -0,0 +1,410 @@
+//! Terminal animation of the dial for `--visualize`.
+//!
+//! Long turns are split into at most `MAXIMUM_FRAMES_PER_INSTRUCTION` frames.
+//! Every frame is computed with `Dial::new_dial_position`, so the dial on
+//! screen and the totals always match the regular simulation. Zeros passed
+//! add up across the frames of a turn. A landing on 0 only counts on the last
+//! frame of an instruction.
+
+use crate::dial::{Dial, DialDirection, DialResult, ZeroCountingPolicy, ZeroTally};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::canvas::{Canvas, Circle, Line as CanvasLine};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use std::error::Error;
+use std::f64::consts::TAU;
+use std::time::Duration;
+
+pub const MAXIMUM_FRAMES_PER_INSTRUCTION: u64 = 60;
+
+const INITIAL_FRAME_DELAY: Duration = Duration::from_millis(40);
+const MINIMUM_FRAME_DELAY: Duration = Duration::from_millis(1);
+const MAXIMUM_FRAME_DELAY: Duration = Duration::from_secs(2);
+const IDLE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// One drawn state of the dial.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimationFrame {
+    pub line: usize,
+    pub dial_direction: DialDirection,
+    pub dial_position: u64,
+    /// Zeros passed between the previous frame and this one.
+    pub number_of_zeros_crossed: u64,
+    /// Whether this frame finishes its instruction.
+    pub instruction_done: bool,
+}
+
+struct InProgress {
+    line: usize,
+    dial_direction: DialDirection,
+    remaining_steps: u64,
+    steps_per_frame: u64,
+}
+
+/// Splits instructions into frames, one instruction at a time.
+pub struct Animation {
+    dial: Dial,
+    dial_position: u64,
+    in_progress: Option<InProgress>,
+}
+
+impl Animation {
+    pub fn new(dial: Dial) -> Animation {
+        Animation {
+            dial,
+            dial_position: dial.start(),
+            in_progress: None,
+        }
+    }
+
+    pub fn dial_position(&self) -> u64 {
+        self.dial_position
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.in_progress.is_none()
+    }
+
+    /// Starts animating an instruction; the previous one must have finished.
+    pub fn start(&mut self, line: usize, dial_direction: DialDirection) {
+        assert!(self.is_idle(), "previous instruction is still animating");
+        let steps = match dial_direction {
+            DialDirection::Left(steps) | DialDirection::Right(steps) => steps,
+        };
+        self.in_progress = Some(InProgress {
+            line,
+            dial_direction,
+            remaining_steps: steps,
+            steps_per_frame: steps.div_ceil(MAXIMUM_FRAMES_PER_INSTRUCTION).max(1),
+        });
+    }
+
+    /// Advances the current instruction by one frame, or returns `None` when idle.
+    pub fn next_frame(&mut self) -> Option<AnimationFrame> {
+        let in_progress = self.in_progress.as_mut()?;
+        let steps = in_progress.remaining_steps.min(in_progress.steps_per_frame);
+        let frame_direction = match in_progress.dial_direction {
+            DialDirection::Left(_) => DialDirection::Left(steps),
+            DialDirection::Right(_) => DialDirection::Right(steps),
+        };
+        let current_dial_position = DialResult {
+            dial_position: self.dial_position,
+            number_of_zeros_crossed: 0,
+            number_of_zeros_landed_on: 0,
+        };
+        let dial_result = self.dial.new_dial_position(
+            current_dial_position,
+            frame_direction,
+            ZeroCountingPolicy::Passes,
+        );
+        in_progress.remaining_steps -= steps;
+        self.dial_position = dial_result.dial_position;
+
+        let frame = AnimationFrame {
+            line: in_progress.line,
+            dial_direction: in_progress.dial_direction,
+            dial_position: dial_result.dial_position,
+            number_of_zeros_crossed: dial_result.number_of_zeros_crossed,
+            instruction_done: in_progress.remaining_steps == 0,
+        };
+        if frame.instruction_done {
+            self.in_progress = None;
+        }
+        Some(frame)
+    }
+}
+
+impl AnimationFrame {
+    /// The zero counts this frame contributes to the totals under `policy`.
+    pub fn dial_result(&self, policy: ZeroCountingPolicy) -> DialResult {
+        policy.apply(DialResult {
+            dial_position: self.dial_position,
+            number_of_zeros_crossed: self.number_of_zeros_crossed,
+            number_of_zeros_landed_on: (self.instruction_done && self.dial_position == 0) as u64,
+        })
+    }
+}
+
+struct View {
+    dial: Dial,
+    policy: ZeroCountingPolicy,
+    last_frame: Option<AnimationFrame>,
+    zero_tally: ZeroTally,
+    paused: bool,
+    finished: bool,
+    frame_delay: Duration,
+}
+
+/// Animates `instructions` in the terminal until the user quits and returns
+/// the zero counts of the instructions shown so far.
+pub fn run<I>(
+    dial: &Dial,
+    policy: ZeroCountingPolicy,
+    instructions: I,
+) -> Result<ZeroTally, Box<dyn Error>>
+where
+    I: Iterator<Item = Result<(usize, DialDirection), Box<dyn Error>>>,
+{
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, dial, policy, instructions);
+    ratatui::restore();
+    result
+}
+
+fn event_loop<I>(
+    terminal: &mut DefaultTerminal,
+    dial: &Dial,
+    policy: ZeroCountingPolicy,
+    mut instructions: I,
+) -> Result<ZeroTally, Box<dyn Error>>
+where
+    I: Iterator<Item = Result<(usize, DialDirection), Box<dyn Error>>>,
+{
+    let mut animation = Animation::new(*dial);
+    let mut view = View {
+        dial: *dial,
+        policy,
+        last_frame: None,
+        zero_tally: ZeroTally::default(),
+        paused: false,
+        finished: false,
+        frame_delay: INITIAL_FRAME_DELAY,
+    };
+
+    loop {
+        terminal.draw(|frame| draw(frame, &view))?;
+
+        let running = !view.paused && !view.finished;
+        let timeout = if running {
+            view.frame_delay
+        } else {
+            IDLE_POLL_INTERVAL
+        };
+        let mut single_step = false;
+        if event::poll(timeout)?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(view.zero_tally),
+                KeyCode::Char(' ') | KeyCode::Char('p') => view.paused = !view.paused,
+                KeyCode::Char('n') | KeyCode::Right => {
+                    view.paused = true;
+                    single_step = true;
+                }
+                KeyCode::Char('+') | KeyCode::Char('=') => {
+                    view.frame_delay = (view.frame_delay / 2).max(MINIMUM_FRAME_DELAY)
+                }
+                KeyCode::Char('-') => {
+                    view.frame_delay = (view.frame_delay * 2).min(MAXIMUM_FRAME_DELAY)
+                }
+                _ => {}
+            }
+            if !single_step {
+                continue;
+            }
+        }
+        if view.finished || (view.paused && !single_step) {
+            continue;
+        }
+
+        if animation.is_idle() {
+            match instructions.next() {
+                Some(instruction) => {
+                    let (line, dial_direction) = instruction?;
+                    animation.start(line, dial_direction);
+                }
+                None => {
+                    view.finished = true;
+                    continue;
+                }
+            }
+        }
+        if let Some(frame) = animation.next_frame() {
+            view.zero_tally.add(&frame.dial_result(view.policy))?;
+            view.last_frame = Some(frame);
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, view: &View) {
+    let [dial_area, status_area] =
+        Layout::vertical([Constraint::Min(8), Constraint::Length(5)]).areas(frame.area());
+
+    let dial_position = view
+        .last_frame
+        .map_or(view.dial.start(), |last_frame| last_frame.dial_position);
+    let crossed_zero = view
+        .last_frame
+        .is_some_and(|last_frame| last_frame.number_of_zeros_crossed > 0);
+    let needle_color = if crossed_zero {
+        Color::Red
+    } else {
+        Color::Yellow
+    };
+    // Position 0 is at the top and numbers grow clockwise.
+    let angle = TAU * dial_position as f64 / view.dial.size() as f64;
+
+    let canvas = Canvas::default()
+        .block(Block::bordered().title(format!(" Dial of {} positions ", view.dial.size())))
+        .x_bounds([-1.2, 1.2])
+        .y_bounds([-1.2, 1.2])
+        .paint(move |context| {
+            context.draw(&Circle {
+                x: 0.0,
+                y: 0.0,
+                radius: 1.0,
+                color: Color::White,
+            });
+            context.print(-0.02, 1.08, Span::styled("0", Style::new().bold()));
+            context.draw(&CanvasLine {
+                x1: 0.0,
+                y1: 0.0,
+                x2: 0.9 * angle.sin(),
+                y2: 0.9 * angle.cos(),
+                color: needle_color,
+            });
+        });
+    frame.render_widget(canvas, dial_area);
+
+    let instruction = match view.last_frame {
+        Some(last_frame) => format!("line {}: {}", last_frame.line, last_frame.dial_direction),
+        None => "waiting for the first instruction".to_string(),
+    };
+    let mut status = vec![
+        Line::from(vec![
+            Span::raw(format!("{}   position {}   ", instruction, dial_position)),
+            if crossed_zero {
+                Span::styled(
+                    "passed 0",
+                    Style::new()
+                        .fg(Color::White)
+                        .bg(Color::Red)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                Span::raw("")
+            },
+        ]),
+        Line::from(format!(
+            "zeros passed {}   zeros landed on {}   policy {:?}",
+            view.zero_tally.crossed, view.zero_tally.landed_on, view.policy
+        )),
+        Line::from(format!(
+            "{} ms/frame{}",
+            view.frame_delay.as_millis(),
+            if view.finished {
+                "   finished"
+            } else if view.paused {
+                "   paused"
+            } else {
+                ""
+            }
+        )),
+    ];
+    status.push(Line::from(
+        "space pause/resume   n single step   + faster   - slower   q quit".dim(),
+    ));
+    frame.render_widget(Paragraph::new(status), status_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames(dial: Dial, line: usize, dial_direction: DialDirection) -> Vec<AnimationFrame> {
+        let mut animation = Animation::new(dial);
+        animation.start(line, dial_direction);
+        std::iter::from_fn(|| animation.next_frame()).collect()
+    }
+
+    #[test]
+    fn test_animation_case_1() {
+        let frames = frames(Dial::default(), 1, DialDirection::Left(68));
+        assert_eq!(frames.len(), 34);
+        assert_eq!(frames[0].dial_position, 48);
+        assert_eq!(frames[24].dial_position, 0);
+        assert_eq!(frames[24].number_of_zeros_crossed, 1);
+        let crossed: u64 = frames.iter().map(|f| f.number_of_zeros_crossed).sum();
+        assert_eq!(crossed, 1);
+        assert_eq!(frames.last().unwrap().dial_position, 82);
+        assert!(frames.last().unwrap().instruction_done);
+        assert!(!frames[32].instruction_done);
+    }
+
+    #[test]
+    fn test_animation_case_2() {
+        let dial = Dial::new(1000, 999).unwrap();
+        let frames = frames(dial, 3, DialDirection::Right(1_000_000_001));
+        assert!(frames.len() as u64 <= MAXIMUM_FRAMES_PER_INSTRUCTION);
+        let crossed: u64 = frames.iter().map(|f| f.number_of_zeros_crossed).sum();
+        let expected = dial.dial_right(dial.initial_result(), 1_000_000_001);
+        assert_eq!(crossed, expected.number_of_zeros_crossed);
+        assert_eq!(frames.last().unwrap().dial_position, expected.dial_position);
+    }
+
+    #[test]
+    fn test_animation_case_3() {
+        let frames = frames(Dial::default(), 2, DialDirection::Right(0));
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].dial_position, 50);
+        assert!(frames[0].instruction_done);
+    }
+
+    #[test]
+    fn test_animation_case_4() {
+        use DialDirection::{Left, Right};
+        let sample = [
+            Left(68),
+            Left(30),
+            Right(48),
+            Left(5),
+            Right(60),
+            Left(55),
+            Left(1),
+            Left(99),
+            Right(14),
+            Left(82),
+        ];
+        let mut animation = Animation::new(Dial::default());
+        let mut zero_tally = ZeroTally::default();
+        for (index, dial_direction) in sample.into_iter().enumerate() {
+            animation.start(index + 1, dial_direction);
+            while let Some(frame) = animation.next_frame() {
+                zero_tally
+                    .add(&frame.dial_result(ZeroCountingPolicy::Both))
+                    .unwrap();
+            }
+        }
+        assert_eq!(animation.dial_position(), 32);
+        assert_eq!(
+            zero_tally,
+            ZeroTally {
+                landed_on: 3,
+                crossed: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_animation_frame_dial_result_case_1() {
+        // Passing 0 halfway through an instruction is a crossing, not a landing.
+        let frames = frames(Dial::default(), 1, DialDirection::Right(150));
+        let mut zero_tally = ZeroTally::default();
+        for frame in &frames {
+            zero_tally
+                .add(&frame.dial_result(ZeroCountingPolicy::Both))
+                .unwrap();
+        }
+        assert_eq!(
+            zero_tally,
+            ZeroTally {
+                landed_on: 1,
+                crossed: 2
+            }
+        );
+    }
+}
@@ -0,0 +1,536 @@
+// Sums the invalid IDs of a range without visiting every ID.
+//
+// An ID with `length` digits that is a chunk of `period` digits repeated
+// `length / period` times is `chunk * multiplier`, where the multiplier is
+// 1 followed by `period - 1` zeros, repeated: 10101 for a chunk of 2 digits
+// repeated 3 times. The chunks form a contiguous range, so the IDs of one
+// period inside a range are an arithmetic series with a closed-form sum.
+//
+// A number with period 2 also has period 4, 6, ... so the series overlap.
+// `weights` gives every period a factor from inclusion–exclusion over the
+// divisors of the length, such that each invalid ID is counted exactly once.
+//
+// Nothing here is specific to base 10: in radix `b` the multiplier is
+// (b^length - 1) / (b^period - 1), 0x10101 for two hex digits repeated three
+// times.
+//
+// The same code runs on u128 when the range fits in a u64, where every
+// intermediate value stays below 2^128, and on big integers otherwise.
+
+use crate::RepetitionRule;
+use crate::id::{IdRange, serialize_decimal, to_u64_range};
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::Pow;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::collections::BTreeMap;
+
+/// What the closed-form sums need from an integer type.
+trait Wide: Integer + Clone + From<u64> + Pow<u32, Output = Self> {}
+
+impl<T: Integer + Clone + From<u64> + Pow<u32, Output = T>> Wide for T {}
+
+fn number_of_digits<T: Wide>(value: &T, radix: u32) -> u32 {
+    let radix = T::from(radix as u64);
+    let mut digits = 1;
+    let mut value = value.clone() / radix.clone();
+    while !value.is_zero() {
+        digits += 1;
+        value = value / radix.clone();
+    }
+    digits
+}
+
+fn divisors(n: u32) -> Vec<u32> {
+    (1..=n).filter(|&d| n.is_multiple_of(d)).collect()
+}
+
+fn power<T: Wide>(radix: u32, exponent: u32) -> T {
+    T::from(radix as u64).pow(exponent)
+}
+
+// For IDs of `length` digits, returns a weight for every period such that
+// an ID is counted once when one of the periods the rule allows fits it, and
+// zero times otherwise.
+//
+// An ID whose smallest period is `m` has every multiple of `m` dividing the
+// length as a period too, so it is counted `sum of weight(d) for m | d`
+// times. Walking the divisors from the largest down, each weight is the
+// target count minus what the larger multiples already contribute.
+fn weights(length: u32, rule: &RepetitionRule) -> Vec<(u32, i64)> {
+    let divisors = divisors(length);
+    let allowed: Vec<u32> = divisors
+        .iter()
+        .copied()
+        .filter(|&period| period < length && rule.allows((length / period) as usize))
+        .collect();
+
+    let mut weights: Vec<(u32, i64)> = vec![];
+    for &period in divisors.iter().rev() {
+        let target = allowed.iter().any(|&p| p.is_multiple_of(period)) as i64;
+        let counted: i64 = weights
+            .iter()
+            .filter(|&&(multiple, _)| multiple.is_multiple_of(period))
+            .map(|(_, weight)| weight)
+            .sum();
+        weights.push((period, target - counted));
+    }
+    weights.retain(|&(_, weight)| weight != 0);
+    weights
+}
+
+// The multiplier and the first and last chunk of the IDs between `start` and
+// `end` made of one chunk of `period` digits in `radix` repeated to `length`
+// digits, if there are any.
+fn repeated_chunks<T: Wide>(
+    length: u32,
+    period: u32,
+    radix: u32,
+    start: &T,
+    end: &T,
+) -> Option<(T, T, T)> {
+    let multiplier =
+        (power::<T>(radix, length) - T::one()) / (power::<T>(radix, period) - T::one());
+    let first_chunk = Integer::div_ceil(start, &multiplier).max(power(radix, period - 1));
+    let last_chunk = (end.clone() / multiplier.clone()).min(power::<T>(radix, period) - T::one());
+    (first_chunk <= last_chunk).then_some((multiplier, first_chunk, last_chunk))
+}
+
+// Sum of the IDs between `start` and `end` made of one chunk of `period`
+// digits in `radix` repeated to `length` digits.
+fn sum_of_repeated<T: Wide>(length: u32, period: u32, radix: u32, start: &T, end: &T) -> T {
+    let Some((multiplier, first_chunk, last_chunk)) =
+        repeated_chunks(length, period, radix, start, end)
+    else {
+        return T::zero();
+    };
+    // One of the two factors is even, so halving is exact.
+    let count = last_chunk.clone() - first_chunk.clone() + T::one();
+    multiplier * ((first_chunk + last_chunk) * count / T::from(2))
+}
+
+fn sum_invalid_between<T: Wide>(start: &T, end: &T, rule: &RepetitionRule, radix: u32) -> T {
+    // Kept apart so that unsigned types never go below zero on the way.
+    let mut added = T::zero();
+    let mut removed = T::zero();
+    for length in number_of_digits(start, radix)..=number_of_digits(end, radix) {
+        for (period, weight) in weights(length, rule) {
+            let sum =
+                sum_of_repeated(length, period, radix, start, end) * T::from(weight.unsigned_abs());
+            if weight > 0 {
+                added = added + sum;
+            } else {
+                removed = removed + sum;
+            }
+        }
+    }
+    added - removed
+}
+
+/// Same result as `add_invalid_ranges` for one range and one radix, in time
+/// that depends on the number of digits rather than on the width of the range.
+pub fn sum_invalid_range(range: &IdRange, rule: &RepetitionRule, radix: u32) -> BigUint {
+    if range.is_empty() {
+        return BigUint::ZERO;
+    }
+    match to_u64_range(range) {
+        Some(range) => BigUint::from(sum_invalid_between(
+            &(*range.start() as u128),
+            &(*range.end() as u128),
+            rule,
+            radix,
+        )),
+        None => sum_invalid_between(range.start(), range.end(), rule, radix),
+    }
+}
+
+/// An invalid ID with the chunk that repeats in it when written in `radix`.
+#[derive(Debug, PartialEq)]
+pub struct InvalidId {
+    pub id: BigUint,
+    pub unit: BigUint,
+    pub repeats: usize,
+    pub radix: u32,
+}
+
+// Lets a hand-written `Serialize` impl write a field with `serialize_decimal`.
+struct Decimal<'a>(&'a BigUint);
+
+impl Serialize for Decimal<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_decimal(self.0, serializer)
+    }
+}
+
+/// The ID is a decimal number and the unit a string of digits in `radix`,
+/// the way the text report prints them.
+impl Serialize for InvalidId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("InvalidId", 4)?;
+        state.serialize_field("id", &Decimal(&self.id))?;
+        state.serialize_field("unit", &self.unit.to_str_radix(self.radix))?;
+        state.serialize_field("repeats", &self.repeats)?;
+        state.serialize_field("radix", &self.radix)?;
+        state.end()
+    }
+}
+
+fn invalid_ids_between<T>(start: &T, end: &T, rule: &RepetitionRule, radix: u32) -> Vec<InvalidId>
+where
+    T: Wide,
+    BigUint: From<T>,
+{
+    let mut invalid_ids: BTreeMap<T, InvalidId> = BTreeMap::new();
+    for length in number_of_digits(start, radix)..=number_of_digits(end, radix) {
+        for period in divisors(length) {
+            let repeats = (length / period) as usize;
+            if period == length || !rule.allows(repeats) {
+                continue;
+            }
+            let Some((multiplier, first_chunk, last_chunk)) =
+                repeated_chunks(length, period, radix, start, end)
+            else {
+                continue;
+            };
+            let mut unit = first_chunk;
+            while unit <= last_chunk {
+                let id = unit.clone() * multiplier.clone();
+                invalid_ids.entry(id.clone()).or_insert(InvalidId {
+                    id: BigUint::from(id),
+                    unit: BigUint::from(unit.clone()),
+                    repeats,
+                    radix,
+                });
+                unit = unit + T::one();
+            }
+        }
+    }
+    invalid_ids.into_values().collect()
+}
+
+/// Lists the invalid IDs of `range` in increasing order, built from their
+/// chunks rather than found by checking every ID. An ID that repeats in
+/// several allowed ways is reported with the shortest of the `periods` that
+/// `validate` gives for it, which need not be its primitive unit.
+pub fn invalid_ids(range: &IdRange, rule: &RepetitionRule, radix: u32) -> Vec<InvalidId> {
+    if range.is_empty() {
+        return vec![];
+    }
+    match to_u64_range(range) {
+        Some(range) => invalid_ids_between(
+            &(*range.start() as u128),
+            &(*range.end() as u128),
+            rule,
+            radix,
+        ),
+        None => invalid_ids_between(range.start(), range.end(), rule, radix),
+    }
+}
+
+/// The IDs of `range` that are invalid in at least one of `radixes`, each
+/// reported in the first radix of the list it is invalid in.
+pub fn invalid_ids_in_any(
+    range: &IdRange,
+    rule: &RepetitionRule,
+    radixes: &[u32],
+) -> Vec<InvalidId> {
+    let mut invalid_ids_by_id: BTreeMap<BigUint, InvalidId> = BTreeMap::new();
+    for &radix in radixes {
+        for invalid_id in invalid_ids(range, rule, radix) {
+            invalid_ids_by_id
+                .entry(invalid_id.id.clone())
+                .or_insert(invalid_id);
+        }
+    }
+    invalid_ids_by_id.into_values().collect()
+}
+
+/// Sum of the IDs of `range` that are invalid in at least one of `radixes`.
+///
+/// There is no closed form for the union over several radixes, so the sum
+/// goes through the list of invalid IDs and takes time proportional to it.
+pub fn sum_invalid_range_in_any(
+    range: &IdRange,
+    rule: &RepetitionRule,
+    radixes: &[u32],
+) -> BigUint {
+    match radixes {
+        [radix] => sum_invalid_range(range, rule, *radix),
+        _ => invalid_ids_in_any(range, rule, radixes)
+            .iter()
+            .map(|invalid_id| &invalid_id.id)
+            .sum(),
+    }
+}
+
+// --- weights ---
+
+#[test]
+fn test_weights_case_1() {
+    // Periods 2 and 3 both fit 6 digits; IDs with period 1 have both and must
+    // not be counted twice.
+    let weights = weights(6, &RepetitionRule::AtLeast(2));
+    assert_eq!(weights, vec![(3, 1), (2, 1), (1, -1)]);
+}
+
+#[test]
+fn test_weights_case_2() {
+    let weights = weights(6, &RepetitionRule::Exactly(2));
+    assert_eq!(weights, vec![(3, 1)]);
+}
+
+#[test]
+fn test_weights_case_3() {
+    assert_eq!(weights(7, &RepetitionRule::Exactly(2)), vec![]);
+    assert_eq!(weights(1, &RepetitionRule::AtLeast(2)), vec![]);
+}
+
+// --- sum_invalid_range ---
+
+#[cfg(test)]
+fn sum(range: std::ops::RangeInclusive<u64>, rule: &RepetitionRule) -> u128 {
+    use num_traits::ToPrimitive;
+    sum_invalid_range(&crate::id::from_u64_range(range), rule, 10)
+        .to_u128()
+        .unwrap()
+}
+
+#[cfg(test)]
+fn big(digits: &str) -> BigUint {
+    digits.parse().unwrap()
+}
+
+#[test]
+fn test_sum_invalid_range_case_1() {
+    let rule = RepetitionRule::AtLeast(2);
+    assert_eq!(sum(11..=22, &rule), 33);
+    assert_eq!(sum(95..=115, &rule), 210);
+    assert_eq!(sum(998..=1012, &rule), 2009);
+    assert_eq!(sum(2121212118..=2121212124, &rule), 2121212121);
+}
+
+#[test]
+fn test_sum_invalid_range_case_2() {
+    let rules = [
+        RepetitionRule::Exactly(2),
+        RepetitionRule::Exactly(3),
+        RepetitionRule::AtLeast(2),
+        RepetitionRule::AtLeast(3),
+        RepetitionRule::AtMost(2),
+        RepetitionRule::AtMost(4),
+        RepetitionRule::OneOf([2, 5].into()),
+        RepetitionRule::OneOf([3, 6].into()),
+    ];
+    let ranges = [1..=1, 0..=120_000, 9_999..=10_001, 999_000..=1_001_000];
+    for rule in &rules {
+        for range in &ranges {
+            assert_eq!(
+                sum(range.clone(), rule),
+                crate::add_invalid_ranges(std::slice::from_ref(range), rule, &[10]),
+                "{:?} {:?}",
+                range,
+                rule
+            );
+        }
+    }
+}
+
+#[test]
+fn test_sum_invalid_range_case_3() {
+    // 20-digit IDs are two 10-digit halves; the largest below u64::MAX is
+    // 1844674407 repeated.
+    let rule = RepetitionRule::Exactly(2);
+    assert_eq!(
+        sum(10_000_000_000_000_000_000..=u64::MAX, &rule),
+        12_014_118_354_628_792_115_342_738_028
+    );
+    assert_eq!(sum(u64::MAX..=u64::MAX, &rule), 0);
+}
+
+#[test]
+fn test_sum_invalid_range_case_4() {
+    let range = 18_446_744_071_844_600_000..=18_446_744_071_844_700_000;
+    for rule in [RepetitionRule::Exactly(2), RepetitionRule::AtLeast(2)] {
+        assert_eq!(
+            sum(range.clone(), &rule),
+            crate::add_invalid_ranges(std::slice::from_ref(&range), &rule, &[10])
+        );
+    }
+    assert_eq!(
+        sum(range, &RepetitionRule::Exactly(2)),
+        18_446_744_071_844_674_407
+    );
+}
+
+#[test]
+fn test_sum_invalid_range_case_5() {
+    // A 40-digit ID made of a 20-digit chunk that does not fit in a u64.
+    let id = big("9876543210987654321098765432109876543210");
+    let range = id.clone() - 1000_u32..=id.clone() + 1000_u32;
+    assert_eq!(
+        sum_invalid_range(&range, &RepetitionRule::Exactly(2), 10),
+        id
+    );
+    assert_eq!(
+        sum_invalid_range(&range, &RepetitionRule::AtLeast(2), 10),
+        crate::add_invalid_wide_range(&range, &RepetitionRule::AtLeast(2), &[10])
+    );
+}
+
+#[test]
+fn test_sum_invalid_range_case_6() {
+    // Every 30-digit ID made of a 15-digit chunk repeated twice:
+    // (10^15 + 1) times the sum of all 15-digit chunks.
+    let range = big("100000000000000000000000000000")..=big("999999999999999999999999999999");
+    let chunks = big("999999999999999") * big("1000000000000000") / 2_u32
+        - big("99999999999999") * big("100000000000000") / 2_u32;
+    assert_eq!(
+        sum_invalid_range(&range, &RepetitionRule::Exactly(2), 10),
+        big("1000000000000001") * chunks
+    );
+}
+
+#[test]
+fn test_sum_invalid_range_case_7() {
+    // The u128 and the big integer versions agree where both apply.
+    for rule in [
+        RepetitionRule::AtLeast(2),
+        RepetitionRule::OneOf([2, 4].into()),
+    ] {
+        let (start, end) = (12_345_u64, 9_876_543_210_123_u64);
+        assert_eq!(
+            BigUint::from(sum_invalid_between(
+                &(start as u128),
+                &(end as u128),
+                &rule,
+                10
+            )),
+            sum_invalid_between(&BigUint::from(start), &BigUint::from(end), &rule, 10)
+        );
+    }
+}
+
+#[test]
+fn test_sum_invalid_range_case_8() {
+    // 0x11, 0x22, ... 0xff are the 2-digit hex IDs that repeat.
+    let range = crate::id::from_u64_range(0..=300);
+    assert_eq!(
+        sum_invalid_range(&range, &RepetitionRule::Exactly(2), 16),
+        BigUint::from((1..=15_u32).map(|digit| digit * 17).sum::<u32>())
+    );
+    for radix in [2, 3, 7, 16, 36] {
+        for rule in [RepetitionRule::AtLeast(2), RepetitionRule::Exactly(3)] {
+            let range = 0..=20_000;
+            assert_eq!(
+                sum_invalid_range(&crate::id::from_u64_range(range.clone()), &rule, radix),
+                BigUint::from(crate::add_invalid_ranges(&[range], &rule, &[radix])),
+                "{:?} {}",
+                rule,
+                radix
+            );
+        }
+    }
+}
+
+#[test]
+fn test_sum_invalid_range_in_any_case_1() {
+    let rule = RepetitionRule::AtLeast(2);
+    let range = 0..=50_000;
+    let radixes = [10, 16, 36];
+    assert_eq!(
+        sum_invalid_range_in_any(&crate::id::from_u64_range(range.clone()), &rule, &radixes),
+        BigUint::from(crate::add_invalid_ranges(&[range], &rule, &radixes))
+    );
+}
+
+// --- invalid_ids ---
+
+#[cfg(test)]
+fn listed(range: std::ops::RangeInclusive<u64>, rule: &RepetitionRule) -> Vec<(u64, u64, usize)> {
+    use num_traits::ToPrimitive;
+    invalid_ids(&crate::id::from_u64_range(range), rule, 10)
+        .into_iter()
+        .map(|invalid_id| {
+            (
+                invalid_id.id.to_u64().unwrap(),
+                invalid_id.unit.to_u64().unwrap(),
+                invalid_id.repeats,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_invalid_ids_case_1() {
+    assert_eq!(
+        listed(95..=1111, &RepetitionRule::AtLeast(2)),
+        vec![
+            (99, 9, 2),
+            (111, 1, 3),
+            (222, 2, 3),
+            (333, 3, 3),
+            (444, 4, 3),
+            (555, 5, 3),
+            (666, 6, 3),
+            (777, 7, 3),
+            (888, 8, 3),
+            (999, 9, 3),
+            (1010, 10, 2),
+            (1111, 1, 4),
+        ]
+    );
+}
+
+#[test]
+fn test_invalid_ids_case_2() {
+    let rules = [
+        RepetitionRule::Exactly(2),
+        RepetitionRule::AtLeast(2),
+        RepetitionRule::OneOf([3, 4].into()),
+    ];
+    let range = 0..=120_000_u64;
+    for rule in &rules {
+        let expected: Vec<(u64, u64, usize)> = range
+            .clone()
+            .filter_map(|id| match crate::validate(&id.to_string(), rule) {
+                crate::ValidationResult::Invalid { periods, .. } => {
+                    let digits = id.to_string();
+                    let unit: u64 = digits[..periods[0]].parse().unwrap();
+                    Some((id, unit, digits.len() / periods[0]))
+                }
+                crate::ValidationResult::Valid => None,
+            })
+            .collect();
+        assert_eq!(listed(range.clone(), rule), expected, "{:?}", rule);
+    }
+}
+
+#[test]
+fn test_invalid_ids_case_3() {
+    let unit = big("1234567890123456789012345");
+    let id = big(&unit.to_string().repeat(2));
+    let invalid_ids = invalid_ids(&(id.clone()..=id.clone()), &RepetitionRule::AtLeast(2), 10);
+    assert_eq!(
+        invalid_ids,
+        vec![InvalidId {
+            id,
+            unit,
+            repeats: 2,
+            radix: 10
+        }]
+    );
+}
+
+#[test]
+fn test_invalid_ids_in_any_case_1() {
+    // 33, 44, ... 99 repeat in decimal and 0x22, 0x33, ... 0xff in hex.
+    let range = crate::id::from_u64_range(33..=255);
+    let listed: Vec<(u64, u32)> =
+        invalid_ids_in_any(&range, &RepetitionRule::Exactly(2), &[10, 16])
+            .into_iter()
+            .map(|invalid_id| (invalid_id.id.try_into().unwrap(), invalid_id.radix))
+            .collect();
+    assert_eq!(listed.len(), 7 + 14);
+    assert_eq!(listed[..3], [(33, 10), (34, 16), (44, 10)]);
+    assert_eq!(listed.last(), Some(&(255, 16)));
+}
@@ -0,0 +1,44 @@
+// IDs of any number of digits.
+//
+// Ranges are read and reported as big integers so that 25 to 40 digit IDs
+// work like short ones. The hot loops convert a range to u64 when it fits
+// and only fall back to big integer arithmetic for wider ranges.
+
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+use serde::{Serialize, Serializer};
+use serde_json::value::RawValue;
+use std::ops::RangeInclusive;
+
+pub type IdRange = RangeInclusive<BigUint>;
+
+/// The same range in u64, if both ends fit.
+pub fn to_u64_range(range: &IdRange) -> Option<RangeInclusive<u64>> {
+    Some(range.start().to_u64()?..=range.end().to_u64()?)
+}
+
+#[cfg(test)]
+pub fn from_u64_range(range: RangeInclusive<u64>) -> IdRange {
+    BigUint::from(*range.start())..=BigUint::from(*range.end())
+}
+
+/// Writes a big integer as a plain JSON number with all its digits, rather
+/// than as the list of machine words serde would make of it.
+pub fn serialize_decimal<S: Serializer>(value: &BigUint, serializer: S) -> Result<S::Ok, S::Error> {
+    let number = RawValue::from_string(value.to_string()).map_err(serde::ser::Error::custom)?;
+    number.serialize(serializer)
+}
+
+#[cfg(test)]
+pub fn id_ranges(ranges: Vec<RangeInclusive<u64>>) -> Vec<IdRange> {
+    ranges.into_iter().map(from_u64_range).collect()
+}
+
+// --- to_u64_range ---
+
+#[test]
+fn test_to_u64_range_case_1() {
+    assert_eq!(to_u64_range(&from_u64_range(11..=22)), Some(11..=22));
+    let wide = BigUint::from(1_u32)..=BigUint::from(u64::MAX) + 1_u32;
+    assert_eq!(to_u64_range(&wide), None);
+}
@@ -0,0 +1,85 @@
+// Where the range lists come from.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+#[derive(Debug, PartialEq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            Source::Stdin => {
+                let mut content = String::new();
+                io::stdin().read_to_string(&mut content)?;
+                Ok(content)
+            }
+            Source::File(path) => fs::read_to_string(path),
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Stdin => write!(f, "<stdin>"),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Turns the `--input-file` arguments into sources, in order: `-` is
+/// standard input, an argument with `*`, `?` or `[` is a glob whose matches
+/// are taken in alphabetical order, and anything else is a plain path.
+pub fn expand_sources(arguments: &[String]) -> Result<Vec<Source>, Box<dyn Error>> {
+    let mut sources = vec![];
+    for argument in arguments {
+        if argument == "-" {
+            sources.push(Source::Stdin);
+        } else if argument.contains(['*', '?', '[']) {
+            let mut matches = glob::glob(argument)?.collect::<Result<Vec<_>, _>>()?;
+            if matches.is_empty() {
+                return Err(format!("no input file matches '{}'", argument).into());
+            }
+            matches.sort();
+            sources.extend(matches.into_iter().map(Source::File));
+        } else {
+            sources.push(Source::File(PathBuf::from(argument)));
+        }
+    }
+    Ok(sources)
+}
+
+// --- expand_sources ---
+
+#[test]
+fn test_expand_sources_case_1() {
+    let directory = tempfile::tempdir().unwrap();
+    for name in ["b.txt", "a.txt", "c.csv"] {
+        fs::write(directory.path().join(name), "1-2").unwrap();
+    }
+    let pattern = directory.path().join("*.txt").display().to_string();
+    let arguments = vec!["-".to_string(), pattern, "plain.txt".to_string()];
+    assert_eq!(
+        expand_sources(&arguments).unwrap(),
+        vec![
+            Source::Stdin,
+            Source::File(directory.path().join("a.txt")),
+            Source::File(directory.path().join("b.txt")),
+            Source::File(PathBuf::from("plain.txt")),
+        ]
+    );
+}
+
+#[test]
+fn test_expand_sources_case_2() {
+    let directory = tempfile::tempdir().unwrap();
+    let pattern = directory.path().join("*.txt").display().to_string();
+    assert!(expand_sources(&[pattern]).is_err());
+}
@@ -1,190 +1,566 @@
-use clap::Parser;
+mod arithmetic;
+mod id;
+mod input;
+mod parallel;
+mod parse;
+mod report;
+mod serve;
+
+use arithmetic::sum_invalid_range_in_any;
+use clap::{Parser, Subcommand};
+use id::IdRange;
+use input::expand_sources;
+use num_bigint::BigUint;
+use parallel::{CHUNK_SIZE, RangeTotal, add_invalid_ranges_parallel};
+use parse::ranges_from_string;
+use rangeset::RangeSet;
+use report::{ListFormat, RangeReport, write_report};
+use serve::{Listener, ServeOptions};
 use std::cmp::PartialEq;
+use std::collections::BTreeSet;
 use std::error::Error;
-use std::fs;
-use std::io;
+use std::io::{self, BufWriter, Write};
+use std::net::SocketAddr;
 use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::process;
+use std::str::FromStr;
+use std::time::Duration;
 
 #[derive(Parser, Debug)]
 struct Args {
-    #[arg(short, long, default_value = "input.txt")]
-    input_file: String,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Files with ranges separated by commas or newlines; accepts globs and `-` for stdin.
+    #[arg(short, long, num_args = 1.., default_value = "input.txt")]
+    input_file: Vec<String>,
+
+    /// Merge overlapping ranges before listing or timing them. The result
+    /// counts an ID listed in several ranges once either way.
+    #[arg(long)]
+    merge_overlapping: bool,
+
+    /// How many times the chunk must repeat for an ID to be invalid:
+    /// `exactly:N`, `at-least:N`, `at-most:N` or a list of counts such as `2,3,5`.
+    #[arg(short, long, global = true, default_value = "at-least:2")]
+    repeats: RepetitionRule,
+
+    /// Radix the IDs are written in, from 2 to 36. With several, such as
+    /// `10,16,36`, an ID is invalid if it repeats in any of them.
+    #[arg(
+        short = 'b',
+        long = "base",
+        global = true,
+        value_name = "BASE",
+        value_delimiter = ',',
+        default_value = "10",
+        value_parser = clap::value_parser!(u32).range(2..=36)
+    )]
+    radixes: Vec<u32>,
+
+    /// Check every ID one by one instead of summing the invalid IDs arithmetically.
+    #[arg(long, global = true)]
+    enumerate: bool,
+
+    /// Threads used by --enumerate; defaults to one per core.
+    #[arg(long, requires = "enumerate")]
+    threads: Option<usize>,
+
+    /// Print how long each range took on stderr.
+    #[arg(long)]
+    timings: bool,
+
+    /// Print every invalid ID of every range with its repeating unit and repeat count.
+    #[arg(long, conflicts_with_all = ["enumerate", "timings"])]
+    list: bool,
+
+    /// Output format of --list.
+    #[arg(long, value_enum, default_value_t = ListFormat::Text, requires = "list")]
+    format: ListFormat,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Answer requests for single IDs or range sums, one per line, until stopped.
+    Serve(ServeArgs),
+}
+
+#[derive(clap::Args, Debug)]
+#[command(group(clap::ArgGroup::new("address").required(true).args(["listen", "socket"])))]
+struct ServeArgs {
+    /// Localhost address to listen on, such as `127.0.0.1:7002`.
+    #[arg(long)]
+    listen: Option<SocketAddr>,
+
+    /// Path of a Unix socket to listen on; it must not exist yet.
+    #[arg(long)]
+    socket: Option<PathBuf>,
+
+    /// Most connections served at once; further clients are turned away.
+    #[arg(long, default_value_t = 64)]
+    max_connections: usize,
+
+    /// Seconds a client may stay silent before its connection is closed.
+    #[arg(long, default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..))]
+    idle_timeout: u64,
+}
+
+/// Which repeat counts make an ID invalid. A chunk always repeats at least
+/// twice, so `AtMost(3)` allows 2 or 3 repeats.
+#[derive(Clone, PartialEq, Debug)]
+enum RepetitionRule {
+    Exactly(usize),
+    AtLeast(usize),
+    AtMost(usize),
+    OneOf(BTreeSet<usize>),
+}
+
+impl RepetitionRule {
+    fn allows(&self, repeats: usize) -> bool {
+        match self {
+            RepetitionRule::Exactly(n) => repeats == *n,
+            RepetitionRule::AtLeast(n) => repeats >= *n,
+            RepetitionRule::AtMost(n) => repeats <= *n,
+            RepetitionRule::OneOf(counts) => counts.contains(&repeats),
+        }
+    }
+}
+
+impl FromStr for RepetitionRule {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let count = |text: &str| match text.trim().parse::<usize>() {
+            Ok(count) if count >= 2 => Ok(count),
+            _ => Err(format!(
+                "invalid repeat count '{}', expected a number from 2",
+                text
+            )),
+        };
+        match input.split_once(':') {
+            Some(("exactly", n)) => Ok(RepetitionRule::Exactly(count(n)?)),
+            Some(("at-least", n)) => Ok(RepetitionRule::AtLeast(count(n)?)),
+            Some(("at-most", n)) => Ok(RepetitionRule::AtMost(count(n)?)),
+            Some((kind, _)) => Err(format!(
+                "unknown repetition rule '{}', expected exactly, at-least or at-most",
+                kind
+            )),
+            None => Ok(RepetitionRule::OneOf(
+                input.split(',').map(count).collect::<Result<_, _>>()?,
+            )),
+        }
+    }
 }
 
 // I need to create a function that
 // takes as input:
 // - String
-// returns back whether it is invalid and its integer representation.
+// returns back whether it is invalid and the chunk that repeats in it.
 
+/// An ID is invalid when it is a chunk repeated a number of times the rule
+/// allows. `unit` is the shortest chunk it repeats, kept as text so leading
+/// zeros survive, and `periods` are the chunk lengths, all multiples of
+/// `period`, whose repeat counts the rule allows.
 #[derive(PartialEq, Debug)]
 enum ValidationResult {
-    Invalid { number: u64 },
+    Invalid {
+        unit: String,
+        period: usize,
+        repeats: usize,
+        periods: Vec<usize>,
+    },
     Valid,
 }
 
-fn validate(input: &str) -> ValidationResult {
+// For every prefix of `input`, the length of its longest proper prefix that
+// is also a suffix of it.
+fn prefix_function(input: &[u8]) -> Vec<usize> {
+    let mut prefix = vec![0; input.len()];
+    for i in 1..input.len() {
+        let mut k = prefix[i - 1];
+        while k > 0 && input[i] != input[k] {
+            k = prefix[k - 1];
+        }
+        if input[i] == input[k] {
+            k += 1;
+        }
+        prefix[i] = k;
+    }
+    prefix
+}
+
+fn validate(input: &str, rule: &RepetitionRule) -> ValidationResult {
     let length = input.len();
     if length <= 1 {
         return ValidationResult::Valid;
     }
 
-    let max = length / 2;
-    // for each number from 1 to max, including,
-    // if the number divides the length without remainder
-    // then we do the division and we compare all the parts
-    // of the input. If they match, then invalid range.
-    // The first part returned as number.
-    for i in 1..=max {
-        let remainder = length % i;
-        if remainder == 0 {
-            let mut chunks = input.as_bytes().chunks_exact(i);
-            let first = chunks.next().unwrap();
-
-            if chunks.all(|chunk| chunk == first) {
-                if let Some(number) = std::str::from_utf8(first)
-                    .ok()
-                    .and_then(|s| s.parse::<u64>().ok())
-                {
-                    return ValidationResult::Invalid { number };
-                }
-            }
-        }
+    // The input is a chunk repeated exactly when its shortest period, the
+    // length minus the longest border, divides the length. Every other
+    // chunk it repeats is that one repeated in turn.
+    let border = prefix_function(input.as_bytes())[length - 1];
+    let period = length - border;
+    if period == length || !length.is_multiple_of(period) {
+        return ValidationResult::Valid;
+    }
+    let periods: Vec<usize> = (period..length)
+        .step_by(period)
+        .filter(|&multiple| length.is_multiple_of(multiple) && rule.allows(length / multiple))
+        .collect();
+    if periods.is_empty() {
+        return ValidationResult::Valid;
+    }
+    ValidationResult::Invalid {
+        unit: input[..period].to_string(),
+        period,
+        repeats: length / period,
+        periods,
     }
-
-    ValidationResult::Valid
-}
-
-fn range_from_string(input: &str) -> RangeInclusive<u64> {
-    let (left, right) = input.split_once("-").unwrap();
-    let start: u64 = left.parse().unwrap();
-    let end: u64 = right.parse().unwrap();
-    start..=end
 }
 
-fn ranges_from_string(input: &str) -> Vec<RangeInclusive<u64>> {
-    input.split(',').map(range_from_string).collect()
+// The digits of `value` in `radix`, lowercase like `BigUint::to_str_radix`.
+fn to_radix_string(mut value: u64, radix: u32) -> String {
+    if radix == 10 {
+        return value.to_string();
+    }
+    let mut digits = vec![];
+    loop {
+        let digit = (value % radix as u64) as u32;
+        digits.push(char::from_digit(digit, radix).unwrap());
+        value /= radix as u64;
+        if value == 0 {
+            break;
+        }
+    }
+    digits.iter().rev().collect()
 }
 
-fn add_invalid_ranges(ranges: &[RangeInclusive<u64>]) -> u64 {
+/// Sums the IDs that are invalid in at least one of `radixes`.
+fn add_invalid_ranges(
+    ranges: &[RangeInclusive<u64>],
+    rule: &RepetitionRule,
+    radixes: &[u32],
+) -> u128 {
     ranges
         .iter()
         .flat_map(|range| range.clone())
-        .map(|value| match validate(&value.to_string()) {
-            ValidationResult::Invalid { .. } => value,
-            ValidationResult::Valid => 0,
+        .filter(|&value| {
+            radixes.iter().any(|&radix| {
+                validate(&to_radix_string(value, radix), rule) != ValidationResult::Valid
+            })
         })
+        .map(|value| value as u128)
         .sum()
 }
 
-fn read_line(path: &str) -> io::Result<String> {
-    let content = fs::read_to_string(path)?;
-    Ok(content.trim_end().to_string())
+// Same as `add_invalid_ranges` for IDs that do not fit in a u64.
+fn add_invalid_wide_range(range: &IdRange, rule: &RepetitionRule, radixes: &[u32]) -> BigUint {
+    let mut sum = BigUint::ZERO;
+    let mut value = range.start().clone();
+    while value <= *range.end() {
+        if radixes
+            .iter()
+            .any(|&radix| validate(&value.to_str_radix(radix), rule) != ValidationResult::Valid)
+        {
+            sum += &value;
+        }
+        value += 1_u32;
+    }
+    sum
+}
+
+/// Sums the invalid IDs of every range on its own, arithmetically or, with
+/// `enumerate`, by checking each ID on the current rayon thread pool.
+fn range_totals(
+    ranges: &[IdRange],
+    rule: &RepetitionRule,
+    radixes: &[u32],
+    enumerate: bool,
+) -> Vec<RangeTotal> {
+    if enumerate {
+        add_invalid_ranges_parallel(ranges, rule, radixes, CHUNK_SIZE)
+    } else {
+        ranges
+            .iter()
+            .map(|range| {
+                RangeTotal::measure(range, || sum_invalid_range_in_any(range, rule, radixes))
+            })
+            .collect()
+    }
+}
+
+fn serve(serve_args: &ServeArgs, options: ServeOptions) -> Result<(), Box<dyn Error>> {
+    let listener = match (&serve_args.listen, &serve_args.socket) {
+        (Some(address), _) => Listener::tcp(*address)?,
+        (None, Some(path)) => Listener::unix(path)?,
+        (None, None) => unreachable!("clap requires --listen or --socket"),
+    };
+    listener.serve(options);
+    Ok(())
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
     let args = Args::parse();
-    let input_file = args.input_file;
-    let line = read_line(&input_file)?;
-    if line.is_empty() {
+    if let Some(Command::Serve(serve_args)) = &args.command {
+        return serve(
+            serve_args,
+            ServeOptions {
+                rule: args.repeats,
+                radixes: args.radixes,
+                enumerate: args.enumerate,
+                max_connections: serve_args.max_connections,
+                idle_timeout: Duration::from_secs(serve_args.idle_timeout),
+            },
+        );
+    }
+    let mut ranges = vec![];
+    let sources = expand_sources(&args.input_file).unwrap_or_else(|error| {
+        eprintln!("{}", error);
+        process::exit(1);
+    });
+    for source in sources {
+        let content = source.read().unwrap_or_else(|error| {
+            eprintln!("{}: {}", source, error);
+            process::exit(1);
+        });
+        match ranges_from_string(&content) {
+            Ok(source_ranges) => ranges.extend(source_ranges),
+            Err(error) => {
+                eprintln!("{}: {}", source, error);
+                process::exit(1);
+            }
+        }
+    }
+    if ranges.is_empty() {
+        return Ok(());
+    }
+    if args.merge_overlapping {
+        ranges = RangeSet::from_iter(ranges).into_ranges();
+    }
+    if args.list {
+        let range_reports: Vec<RangeReport> = ranges
+            .iter()
+            .map(|range| RangeReport::new(range, &args.repeats, &args.radixes))
+            .collect();
+        let mut out = BufWriter::new(io::stdout().lock());
+        write_report(&mut out, args.format, &range_reports)?;
+        out.flush()?;
         return Ok(());
     }
-    let ranges = ranges_from_string(&line);
-    let result = add_invalid_ranges(&ranges);
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(args.threads.unwrap_or(0))
+        .build()?;
+    let totals_of = |ranges: &[IdRange]| {
+        pool.install(|| range_totals(ranges, &args.repeats, &args.radixes, args.enumerate))
+    };
+    let total = |range_totals: &[RangeTotal]| -> BigUint {
+        range_totals
+            .iter()
+            .map(|range_total| &range_total.sum)
+            .sum()
+    };
+    // The result is taken over the merged ranges so an ID listed in several
+    // ranges counts once, while --timings reports the ranges as listed.
+    let merged_ranges = RangeSet::from_iter(ranges.iter().cloned()).into_ranges();
+    let result = if args.timings {
+        let listed_totals = totals_of(&ranges);
+        for range_total in &listed_totals {
+            eprintln!(
+                "{}-{}: {} in {:.3?}",
+                range_total.range.start(),
+                range_total.range.end(),
+                range_total.sum,
+                range_total.elapsed
+            );
+        }
+        if merged_ranges.len() == ranges.len() {
+            // Nothing was merged, so no ID is in two ranges.
+            total(&listed_totals)
+        } else {
+            total(&totals_of(&merged_ranges))
+        }
+    } else {
+        total(&totals_of(&merged_ranges))
+    };
     println!("result = {}", result);
     Ok(())
 }
 
 // ----- validate() -----------------------------------------------------------------------
 
+#[cfg(test)]
+fn invalid(unit: &str, repeats: usize, periods: Vec<usize>) -> ValidationResult {
+    ValidationResult::Invalid {
+        unit: unit.to_string(),
+        period: unit.len(),
+        repeats,
+        periods,
+    }
+}
+
 #[test]
 fn test_case_1() {
     let input = "55";
-    let validation_result = validate(input);
-    assert_eq!(validation_result, ValidationResult::Invalid { number: 5 });
+    let validation_result = validate(input, &RepetitionRule::AtLeast(2));
+    assert_eq!(validation_result, invalid("5", 2, vec![1]));
 }
 
 #[test]
 fn test_case_2() {
     let input = "12";
-    let validation_result = validate(input);
+    let validation_result = validate(input, &RepetitionRule::AtLeast(2));
     assert_eq!(validation_result, ValidationResult::Valid);
 }
 
 #[test]
 fn test_case_3() {
     let input = "1";
-    let validation_result = validate(input);
+    let validation_result = validate(input, &RepetitionRule::AtLeast(2));
     assert_eq!(validation_result, ValidationResult::Valid);
 }
 
 #[test]
 fn test_case_4() {
     let input = "";
-    let validation_result = validate(input);
+    let validation_result = validate(input, &RepetitionRule::AtLeast(2));
     assert_eq!(validation_result, ValidationResult::Valid);
 }
 
 #[test]
 fn test_case_5() {
     let input = "6464";
-    let validation_result = validate(input);
-    assert_eq!(validation_result, ValidationResult::Invalid { number: 64 });
+    let validation_result = validate(input, &RepetitionRule::AtLeast(2));
+    assert_eq!(validation_result, invalid("64", 2, vec![2]));
 }
 
 #[test]
 fn test_case_6() {
     let input = "123123";
-    let validation_result = validate(input);
-    assert_eq!(validation_result, ValidationResult::Invalid { number: 123 });
+    let validation_result = validate(input, &RepetitionRule::AtLeast(2));
+    assert_eq!(validation_result, invalid("123", 2, vec![3]));
 }
 
 #[test]
 fn test_case_7() {
     let input = "101";
-    let validation_result = validate(input);
+    let validation_result = validate(input, &RepetitionRule::AtLeast(2));
     assert_eq!(validation_result, ValidationResult::Valid);
 }
 
 #[test]
 fn test_case_8() {
     let input = "16464";
-    let validation_result = validate(input);
+    let validation_result = validate(input, &RepetitionRule::AtLeast(2));
     assert_eq!(validation_result, ValidationResult::Valid);
 }
 
 #[test]
 fn test_case_9() {
     let input = "64641";
-    let validation_result = validate(input);
+    let validation_result = validate(input, &RepetitionRule::AtLeast(2));
     assert_eq!(validation_result, ValidationResult::Valid);
 }
 
 #[test]
 fn test_case_10() {
     let input = "64164";
-    let validation_result = validate(input);
+    let validation_result = validate(input, &RepetitionRule::AtLeast(2));
     assert_eq!(validation_result, ValidationResult::Valid);
 }
 
-// --- range_from_string -------------------------
+#[test]
+fn test_validate_case_1() {
+    let rule = RepetitionRule::Exactly(2);
+    assert_eq!(validate("6464", &rule), invalid("64", 2, vec![2]));
+    assert_eq!(validate("111", &rule), ValidationResult::Valid);
+    assert_eq!(validate("1111", &rule), invalid("1", 4, vec![2]));
+}
+
+#[test]
+fn test_validate_case_2() {
+    let rule = RepetitionRule::AtLeast(3);
+    assert_eq!(validate("6464", &rule), ValidationResult::Valid);
+    assert_eq!(validate("646464", &rule), invalid("64", 3, vec![2]));
+}
+
+#[test]
+fn test_validate_case_3() {
+    let rule = RepetitionRule::AtMost(2);
+    assert_eq!(validate("777", &rule), ValidationResult::Valid);
+    assert_eq!(validate("777777", &rule), invalid("7", 6, vec![3]));
+}
+
+#[test]
+fn test_validate_case_4() {
+    let rule = RepetitionRule::OneOf(BTreeSet::from([3, 5]));
+    assert_eq!(validate("1212", &rule), ValidationResult::Valid);
+    assert_eq!(validate("121212", &rule), invalid("12", 3, vec![2]));
+    assert_eq!(validate("99999", &rule), invalid("9", 5, vec![1]));
+}
+
+#[test]
+fn test_validate_case_5() {
+    let rule = RepetitionRule::AtLeast(2);
+    assert_eq!(validate("abab", &rule), invalid("ab", 2, vec![2]));
+    assert_eq!(validate("zz", &rule), invalid("z", 2, vec![1]));
+    assert_eq!(validate("110", &rule), ValidationResult::Valid);
+}
+
+#[test]
+fn test_validate_case_6() {
+    // The unit keeps its leading zero and every allowed period is listed.
+    let rule = RepetitionRule::AtLeast(2);
+    assert_eq!(validate("0101", &rule), invalid("01", 2, vec![2]));
+    assert_eq!(validate("00000000", &rule), invalid("0", 8, vec![1, 2, 4]));
+    assert_eq!(
+        validate(
+            "121212121212",
+            &RepetitionRule::OneOf(BTreeSet::from([2, 3]))
+        ),
+        invalid("12", 6, vec![4, 6])
+    );
+    assert_eq!(validate("1231231", &rule), ValidationResult::Valid);
+}
+
+// --- prefix_function ---
 
 #[test]
-fn test_range_from_string_case_1() {
-    let input = "11-25";
-    let range_result = range_from_string(input);
-    assert_eq!(range_result, 11..=25);
+fn test_prefix_function_case_1() {
+    assert_eq!(prefix_function(b"abcabcab"), vec![0, 0, 0, 1, 2, 3, 4, 5]);
+    assert_eq!(prefix_function(b"aabaaab"), vec![0, 1, 0, 1, 2, 2, 3]);
+    assert_eq!(prefix_function(b""), Vec::<usize>::new());
 }
 
-// --- ranges_from_string ---------------------
+// --- to_radix_string ---
 
 #[test]
-fn test_ranges_from_string_case_1() {
-    let input = "11-22,95-115,998-1012,1188511880-1188511890";
-    let ranges = ranges_from_string(input);
+fn test_to_radix_string_case_1() {
+    assert_eq!(to_radix_string(0, 2), "0");
+    assert_eq!(to_radix_string(10, 2), "1010");
+    assert_eq!(to_radix_string(255, 16), "ff");
+    assert_eq!(to_radix_string(u64::MAX, 36), "3w5e11264sgsf");
+    assert_eq!(to_radix_string(1234, 10), "1234");
+}
+
+// --- RepetitionRule::from_str ---
+
+#[test]
+fn test_repetition_rule_from_str_case_1() {
+    assert_eq!("exactly:2".parse(), Ok(RepetitionRule::Exactly(2)));
+    assert_eq!("at-least:3".parse(), Ok(RepetitionRule::AtLeast(3)));
+    assert_eq!("at-most:4".parse(), Ok(RepetitionRule::AtMost(4)));
     assert_eq!(
-        ranges,
-        vec![11..=22, 95..=115, 998..=1012, 1188511880..=1188511890,]
-    )
+        "2,3, 5".parse(),
+        Ok(RepetitionRule::OneOf(BTreeSet::from([2, 3, 5])))
+    );
+}
+
+#[test]
+fn test_repetition_rule_from_str_case_2() {
+    assert!("exactly:1".parse::<RepetitionRule>().is_err());
+    assert!("between:2".parse::<RepetitionRule>().is_err());
+    assert!("2,,3".parse::<RepetitionRule>().is_err());
 }
 
 // --- add_invalid_ranges ---
@@ -192,34 +568,59 @@ fn test_ranges_from_string_case_1() {
 #[test]
 fn add_invalid_ranges_case_1() {
     let ranges = vec![11..=22];
-    let result = add_invalid_ranges(&ranges);
+    let result = add_invalid_ranges(&ranges, &RepetitionRule::AtLeast(2), &[10]);
     assert_eq!(result, 33);
 }
 
 #[test]
 fn add_invalid_ranges_case_2() {
     let ranges = vec![99..=115];
-    let result = add_invalid_ranges(&ranges);
+    let result = add_invalid_ranges(&ranges, &RepetitionRule::AtLeast(2), &[10]);
     assert_eq!(result, 210);
 }
 
 #[test]
 fn add_invalid_ranges_case_3() {
     let ranges = vec![998..=1012];
-    let result = add_invalid_ranges(&ranges);
+    let result = add_invalid_ranges(&ranges, &RepetitionRule::AtLeast(2), &[10]);
     assert_eq!(result, 2009);
 }
 
 #[test]
 fn add_invalid_ranges_case_4() {
     let ranges = vec![565653..=565659];
-    let result = add_invalid_ranges(&ranges);
+    let result = add_invalid_ranges(&ranges, &RepetitionRule::AtLeast(2), &[10]);
     assert_eq!(result, 565656);
 }
 
 #[test]
 fn add_invalid_ranges_case_5() {
     let ranges = vec![824824821..=824824827];
-    let result = add_invalid_ranges(&ranges);
+    let result = add_invalid_ranges(&ranges, &RepetitionRule::AtLeast(2), &[10]);
     assert_eq!(result, 824824824);
 }
+
+#[test]
+fn add_invalid_ranges_case_6() {
+    let ranges = vec![95..=115, 998..=1012];
+    let result = add_invalid_ranges(&ranges, &RepetitionRule::Exactly(2), &[10]);
+    assert_eq!(result, 99 + 1010);
+}
+
+#[test]
+fn add_invalid_ranges_case_7() {
+    // 10 is 1010 in binary and 255 is ff in hex; 11 repeats in decimal only.
+    let ranges = vec![10..=11, 255..=255];
+    assert_eq!(
+        add_invalid_ranges(&ranges, &RepetitionRule::Exactly(2), &[2]),
+        10 + 255
+    );
+    assert_eq!(
+        add_invalid_ranges(&ranges, &RepetitionRule::Exactly(2), &[16]),
+        255
+    );
+    assert_eq!(
+        add_invalid_ranges(&ranges, &RepetitionRule::Exactly(2), &[10, 16]),
+        11 + 255
+    );
+}
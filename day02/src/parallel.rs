@@ -0,0 +1,163 @@
+// Brute-force evaluation of wide ranges on all cores.
+//
+// Every range is cut into chunks of `chunk_size` IDs that are checked in
+// parallel. The ranges themselves are evaluated one after another so each
+// one can be timed on its own. The per-chunk sums are exact integers, so the
+// total does not depend on how the chunks were scheduled.
+
+use crate::id::{IdRange, to_u64_range};
+use crate::{RepetitionRule, add_invalid_ranges, add_invalid_wide_range};
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+use rayon::prelude::*;
+use std::ops::RangeInclusive;
+use std::time::{Duration, Instant};
+
+/// Number of IDs one task checks; large enough to amortise scheduling.
+pub const CHUNK_SIZE: u64 = 1 << 16;
+
+/// The sum of the invalid IDs of one range and how long it took to compute.
+#[derive(Debug)]
+pub struct RangeTotal {
+    pub range: IdRange,
+    pub sum: BigUint,
+    pub elapsed: Duration,
+}
+
+impl RangeTotal {
+    pub fn measure(range: &IdRange, sum: impl FnOnce() -> BigUint) -> RangeTotal {
+        let started = Instant::now();
+        let sum = sum();
+        RangeTotal {
+            range: range.clone(),
+            sum,
+            elapsed: started.elapsed(),
+        }
+    }
+}
+
+fn chunk(range: &RangeInclusive<u64>, chunk_size: u64, index: u64) -> RangeInclusive<u64> {
+    let start = range.start() + index * chunk_size;
+    let end = start.saturating_add(chunk_size - 1).min(*range.end());
+    start..=end
+}
+
+fn wide_chunk(range: &IdRange, chunk_size: u64, index: u64) -> IdRange {
+    let start = range.start() + BigUint::from(index) * chunk_size;
+    let end = (&start + (chunk_size - 1)).min(range.end().clone());
+    start..=end
+}
+
+fn add_invalid_range_parallel(
+    range: &IdRange,
+    rule: &RepetitionRule,
+    radixes: &[u32],
+    chunk_size: u64,
+) -> BigUint {
+    if range.is_empty() {
+        return BigUint::ZERO;
+    }
+    if let Some(range) = to_u64_range(range) {
+        let number_of_chunks = (range.end() - range.start()) / chunk_size + 1;
+        let sum: u128 = (0..number_of_chunks)
+            .into_par_iter()
+            .map(|index| add_invalid_ranges(&[chunk(&range, chunk_size, index)], rule, radixes))
+            .sum();
+        return BigUint::from(sum);
+    }
+    // A range with more chunks than a u64 can count would never finish anyway.
+    let number_of_chunks = ((range.end() - range.start()) / chunk_size + 1_u32)
+        .to_u64()
+        .unwrap_or(u64::MAX);
+    (0..number_of_chunks)
+        .into_par_iter()
+        .map(|index| add_invalid_wide_range(&wide_chunk(range, chunk_size, index), rule, radixes))
+        .sum()
+}
+
+/// Same sums as `add_invalid_ranges`, one `RangeTotal` per range, computed on
+/// the current rayon thread pool.
+pub fn add_invalid_ranges_parallel(
+    ranges: &[IdRange],
+    rule: &RepetitionRule,
+    radixes: &[u32],
+    chunk_size: u64,
+) -> Vec<RangeTotal> {
+    assert!(chunk_size > 0, "chunk size should be greater than 0");
+    ranges
+        .iter()
+        .map(|range| {
+            RangeTotal::measure(range, || {
+                add_invalid_range_parallel(range, rule, radixes, chunk_size)
+            })
+        })
+        .collect()
+}
+
+// --- add_invalid_ranges_parallel ---
+
+#[cfg(test)]
+use crate::id::id_ranges;
+
+#[test]
+fn test_add_invalid_ranges_parallel_case_1() {
+    let ranges = id_ranges(vec![11..=22, 95..=115, 998..=1012, 565653..=565659]);
+    let rule = RepetitionRule::AtLeast(2);
+    for chunk_size in [1, 3, 7, 1000] {
+        let range_totals = add_invalid_ranges_parallel(&ranges, &rule, &[10], chunk_size);
+        let sums: Vec<u128> = range_totals
+            .iter()
+            .map(|total| total.sum.to_u128().unwrap())
+            .collect();
+        assert_eq!(sums, vec![33, 210, 2009, 565656]);
+        assert_eq!(range_totals[2].range, ranges[2]);
+    }
+}
+
+#[test]
+fn test_add_invalid_ranges_parallel_case_2() {
+    let rule = RepetitionRule::Exactly(2);
+    let expected = add_invalid_ranges(&[0..=250_000], &rule, &[10, 16]);
+    for threads in [1, 2, 4] {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        let range_totals = pool.install(|| {
+            add_invalid_ranges_parallel(&id_ranges(vec![0..=250_000]), &rule, &[10, 16], 4096)
+        });
+        assert_eq!(range_totals[0].sum, BigUint::from(expected));
+    }
+}
+
+#[test]
+fn test_add_invalid_ranges_parallel_case_3() {
+    // The last chunk ends at u64::MAX without overflowing.
+    let ranges = id_ranges(vec![u64::MAX - 10..=u64::MAX]);
+    let range_totals = add_invalid_ranges_parallel(&ranges, &RepetitionRule::AtLeast(2), &[10], 4);
+    assert_eq!(range_totals[0].sum, BigUint::ZERO);
+}
+
+#[test]
+fn test_add_invalid_ranges_parallel_case_4() {
+    // 25 digits: 5 digits repeated 5 times, around an ID beyond u64.
+    let id: BigUint = "1234512345123451234512345".parse().unwrap();
+    let ranges = vec![&id - 300_u32..=&id + 300_u32];
+    let range_totals = add_invalid_ranges_parallel(&ranges, &RepetitionRule::AtLeast(2), &[10], 64);
+    assert_eq!(range_totals[0].sum, id);
+}
+
+#[test]
+fn test_chunk_case_1() {
+    assert_eq!(chunk(&(10..=25), 8, 0), 10..=17);
+    assert_eq!(chunk(&(10..=25), 8, 1), 18..=25);
+    assert_eq!(chunk(&(10..=20), 8, 1), 18..=20);
+}
+
+#[test]
+fn test_wide_chunk_case_1() {
+    let start = BigUint::from(u64::MAX);
+    let range = start.clone()..=&start + 20_u32;
+    assert_eq!(wide_chunk(&range, 8, 1), &start + 8_u32..=&start + 15_u32);
+    assert_eq!(wide_chunk(&range, 8, 2), &start + 16_u32..=&start + 20_u32);
+}
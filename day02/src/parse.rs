@@ -0,0 +1,283 @@
+// Parsing of the list of ID ranges, such as `11-22,95-115`.
+//
+// Ranges are separated by commas or newlines, in any mix. Other whitespace is
+// allowed around numbers and separators, and a comma may end a line or the
+// list; only an empty range between two commas is an error. Errors carry the
+// byte offset of the problem in the whole input.
+
+use crate::id::IdRange;
+use num_bigint::BigUint;
+use std::fmt;
+
+#[derive(Debug, PartialEq)]
+pub enum RangeParseErrorReason {
+    EmptyRange,
+    MissingDash,
+    MissingNumber,
+    InvalidCharacter(char),
+    Reversed { start: BigUint, end: BigUint },
+}
+
+impl fmt::Display for RangeParseErrorReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeParseErrorReason::EmptyRange => write!(f, "empty range between commas"),
+            RangeParseErrorReason::MissingDash => {
+                write!(f, "expected a range such as 11-22")
+            }
+            RangeParseErrorReason::MissingNumber => write!(f, "missing number"),
+            RangeParseErrorReason::InvalidCharacter(c) => {
+                write!(f, "invalid character '{}' in number", c)
+            }
+            RangeParseErrorReason::Reversed { start, end } => write!(
+                f,
+                "range {}-{} is reversed, its start should not be greater than its end",
+                start, end
+            ),
+        }
+    }
+}
+
+/// Why a range list could not be parsed, and the byte offset where.
+#[derive(Debug, PartialEq)]
+pub struct RangeParseError {
+    pub offset: usize,
+    pub reason: RangeParseErrorReason,
+}
+
+impl RangeParseError {
+    fn new(offset: usize, reason: RangeParseErrorReason) -> RangeParseError {
+        RangeParseError { offset, reason }
+    }
+
+    pub fn offset_by(self, offset: usize) -> RangeParseError {
+        RangeParseError {
+            offset: self.offset + offset,
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for RangeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "byte {}: {}", self.offset, self.reason)
+    }
+}
+
+impl std::error::Error for RangeParseError {}
+
+fn leading_whitespace(input: &str) -> usize {
+    input.len() - input.trim_start().len()
+}
+
+pub fn number_from_string(input: &str) -> Result<BigUint, RangeParseError> {
+    let offset = leading_whitespace(input);
+    let digits = input.trim();
+    if digits.is_empty() {
+        return Err(RangeParseError::new(
+            offset,
+            RangeParseErrorReason::MissingNumber,
+        ));
+    }
+    if let Some((index, c)) = digits.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        return Err(RangeParseError::new(
+            offset + index,
+            RangeParseErrorReason::InvalidCharacter(c),
+        ));
+    }
+    Ok(BigUint::parse_bytes(digits.as_bytes(), 10).expect("only ASCII digits are left"))
+}
+
+pub fn range_from_string(input: &str) -> Result<IdRange, RangeParseError> {
+    let offset = leading_whitespace(input);
+    if input.trim().is_empty() {
+        return Err(RangeParseError::new(
+            offset,
+            RangeParseErrorReason::EmptyRange,
+        ));
+    }
+    let Some((left, right)) = input.split_once('-') else {
+        return Err(RangeParseError::new(
+            offset,
+            RangeParseErrorReason::MissingDash,
+        ));
+    };
+    let start = number_from_string(left)?;
+    let end = number_from_string(right).map_err(|error| error.offset_by(left.len() + 1))?;
+    if start > end {
+        return Err(RangeParseError::new(
+            offset,
+            RangeParseErrorReason::Reversed { start, end },
+        ));
+    }
+    Ok(start..=end)
+}
+
+pub fn ranges_from_string(input: &str) -> Result<Vec<IdRange>, RangeParseError> {
+    let mut ranges = vec![];
+    let mut segment_start = 0;
+    let mut after_comma = false;
+    let separators = input.match_indices([',', '\n']).chain([(input.len(), "")]);
+    for (offset, separator) in separators {
+        let segment = &input[segment_start..offset];
+        let between_commas = after_comma && separator == ",";
+        if !segment.trim().is_empty() || between_commas {
+            ranges
+                .push(range_from_string(segment).map_err(|error| error.offset_by(segment_start))?);
+        }
+        segment_start = offset + 1;
+        after_comma = separator == ",";
+    }
+    Ok(ranges)
+}
+
+// --- range_from_string -------------------------
+
+#[cfg(test)]
+use crate::id::{from_u64_range, id_ranges};
+
+#[test]
+fn test_range_from_string_case_1() {
+    let input = "11-25";
+    let range_result = range_from_string(input);
+    assert_eq!(range_result, Ok(from_u64_range(11..=25)));
+}
+
+#[test]
+fn test_range_from_string_case_2() {
+    let input = " 11 -\n25 ";
+    let range_result = range_from_string(input);
+    assert_eq!(range_result, Ok(from_u64_range(11..=25)));
+}
+
+#[test]
+fn test_range_from_string_case_3() {
+    let input = "50-10";
+    let range_result = range_from_string(input);
+    assert_eq!(
+        range_result,
+        Err(RangeParseError {
+            offset: 0,
+            reason: RangeParseErrorReason::Reversed {
+                start: 50_u32.into(),
+                end: 10_u32.into()
+            }
+        })
+    );
+}
+
+#[test]
+fn test_range_from_string_case_4() {
+    let input = "11-2x5";
+    let range_result = range_from_string(input);
+    assert_eq!(
+        range_result,
+        Err(RangeParseError {
+            offset: 4,
+            reason: RangeParseErrorReason::InvalidCharacter('x')
+        })
+    );
+}
+
+#[test]
+fn test_range_from_string_case_5() {
+    assert_eq!(
+        range_from_string("1125"),
+        Err(RangeParseError {
+            offset: 0,
+            reason: RangeParseErrorReason::MissingDash
+        })
+    );
+    assert_eq!(
+        range_from_string("11- "),
+        Err(RangeParseError {
+            offset: 4,
+            reason: RangeParseErrorReason::MissingNumber
+        })
+    );
+    assert_eq!(
+        range_from_string("1-123456789012345678901234567890"),
+        Ok(BigUint::from(1_u32)
+            ..=BigUint::parse_bytes(b"123456789012345678901234567890", 10).unwrap())
+    );
+}
+
+// --- ranges_from_string ---------------------
+
+#[test]
+fn test_ranges_from_string_case_1() {
+    let input = "11-22,95-115,998-1012,1188511880-1188511890";
+    let ranges = ranges_from_string(input);
+    assert_eq!(
+        ranges,
+        Ok(id_ranges(vec![
+            11..=22,
+            95..=115,
+            998..=1012,
+            1188511880..=1188511890,
+        ]))
+    )
+}
+
+#[test]
+fn test_ranges_from_string_case_2() {
+    let input = "11-22, 95-115,\n998-1012 ,\n";
+    let ranges = ranges_from_string(input);
+    assert_eq!(ranges, Ok(id_ranges(vec![11..=22, 95..=115, 998..=1012])));
+    assert_eq!(ranges_from_string(""), Ok(vec![]));
+}
+
+#[test]
+fn test_ranges_from_string_case_3() {
+    let input = "11-22,,95-115";
+    let error = ranges_from_string(input).unwrap_err();
+    assert_eq!(
+        error,
+        RangeParseError {
+            offset: 6,
+            reason: RangeParseErrorReason::EmptyRange
+        }
+    );
+    assert_eq!(error.to_string(), "byte 6: empty range between commas");
+}
+
+#[test]
+fn test_ranges_from_string_case_4() {
+    let input = "11-22,95-115,998-101x";
+    let error = ranges_from_string(input).unwrap_err();
+    assert_eq!(
+        error,
+        RangeParseError {
+            offset: 20,
+            reason: RangeParseErrorReason::InvalidCharacter('x')
+        }
+    );
+}
+
+#[test]
+fn test_ranges_from_string_case_5() {
+    let input = "11-22\n95-115,998-1012\n\n  1188511880-1188511890,\n";
+    let ranges = ranges_from_string(input);
+    assert_eq!(
+        ranges,
+        Ok(id_ranges(vec![
+            11..=22,
+            95..=115,
+            998..=1012,
+            1188511880..=1188511890
+        ]))
+    );
+}
+
+#[test]
+fn test_ranges_from_string_case_6() {
+    let input = "11-22\n95-115 50\n";
+    let error = ranges_from_string(input).unwrap_err();
+    assert_eq!(
+        error,
+        RangeParseError {
+            offset: 12,
+            reason: RangeParseErrorReason::InvalidCharacter(' ')
+        }
+    );
+}
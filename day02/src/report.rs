@@ -0,0 +1,201 @@
+// Output of `--list`: every invalid ID of every input range.
+
+use crate::RepetitionRule;
+use crate::arithmetic::{InvalidId, invalid_ids_in_any};
+use crate::id::{IdRange, serialize_decimal};
+use num_bigint::BigUint;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::io::{self, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum ListFormat {
+    Text,
+    Json,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct RangeReport {
+    #[serde(serialize_with = "serialize_decimal")]
+    pub start: BigUint,
+    #[serde(serialize_with = "serialize_decimal")]
+    pub end: BigUint,
+    #[serde(serialize_with = "serialize_decimal")]
+    pub sum: BigUint,
+    pub invalid_ids: Vec<InvalidId>,
+}
+
+impl RangeReport {
+    pub fn new(range: &IdRange, rule: &RepetitionRule, radixes: &[u32]) -> RangeReport {
+        let invalid_ids = invalid_ids_in_any(range, rule, radixes);
+        RangeReport {
+            start: range.start().clone(),
+            end: range.end().clone(),
+            sum: invalid_ids.iter().map(|invalid_id| &invalid_id.id).sum(),
+            invalid_ids,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Report<'a> {
+    ranges: &'a [RangeReport],
+    #[serde(serialize_with = "serialize_decimal")]
+    result: BigUint,
+}
+
+pub fn write_report(
+    out: &mut impl Write,
+    format: ListFormat,
+    range_reports: &[RangeReport],
+) -> io::Result<()> {
+    // An ID listed under several overlapping ranges counts once.
+    let result: BigUint = range_reports
+        .iter()
+        .flat_map(|range_report| &range_report.invalid_ids)
+        .map(|invalid_id| &invalid_id.id)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .sum();
+    match format {
+        ListFormat::Text => {
+            for range_report in range_reports {
+                writeln!(
+                    out,
+                    "{}-{}: {} invalid, sum {}",
+                    range_report.start,
+                    range_report.end,
+                    range_report.invalid_ids.len(),
+                    range_report.sum
+                )?;
+                for invalid_id in &range_report.invalid_ids {
+                    write!(
+                        out,
+                        "  {} = {} x {}",
+                        invalid_id.id,
+                        invalid_id.unit.to_str_radix(invalid_id.radix),
+                        invalid_id.repeats
+                    )?;
+                    if invalid_id.radix == 10 {
+                        writeln!(out)?;
+                    } else {
+                        writeln!(out, " in base {}", invalid_id.radix)?;
+                    }
+                }
+            }
+            writeln!(out, "result = {}", result)
+        }
+        ListFormat::Json => {
+            serde_json::to_writer_pretty(
+                &mut *out,
+                &Report {
+                    ranges: range_reports,
+                    result,
+                },
+            )?;
+            writeln!(out)
+        }
+    }
+}
+
+// --- write_report ---
+
+#[cfg(test)]
+use crate::id::from_u64_range;
+
+#[cfg(test)]
+fn sample_reports() -> Vec<RangeReport> {
+    let rule = RepetitionRule::Exactly(2);
+    vec![
+        RangeReport::new(&from_u64_range(11..=22), &rule, &[10]),
+        RangeReport::new(&from_u64_range(95..=98), &rule, &[10]),
+    ]
+}
+
+#[test]
+fn test_write_report_case_1() {
+    let mut out = vec![];
+    write_report(&mut out, ListFormat::Text, &sample_reports()).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "11-22: 2 invalid, sum 33\n  11 = 1 x 2\n  22 = 2 x 2\n95-98: 0 invalid, sum 0\nresult = 33\n"
+    );
+}
+
+#[test]
+fn test_write_report_case_2() {
+    let mut out = vec![];
+    write_report(&mut out, ListFormat::Json, &sample_reports()).unwrap();
+    let parsed: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(parsed["result"], 33);
+    assert_eq!(parsed["ranges"][0]["start"], 11);
+    assert_eq!(parsed["ranges"][0]["invalid_ids"][1]["id"], 22);
+    assert_eq!(parsed["ranges"][0]["invalid_ids"][1]["unit"], "2");
+    assert_eq!(parsed["ranges"][0]["invalid_ids"][1]["repeats"], 2);
+    assert_eq!(
+        parsed["ranges"][1]["invalid_ids"].as_array().unwrap().len(),
+        0
+    );
+}
+
+#[test]
+fn test_write_report_case_3() {
+    // 21 digits: 7 digits repeated 3 times keeps every digit in JSON.
+    let id: BigUint = "123456712345671234567".parse().unwrap();
+    let range_reports = vec![RangeReport::new(
+        &(&id - 5_u32..=&id + 5_u32),
+        &RepetitionRule::AtLeast(2),
+        &[10],
+    )];
+    let mut out = vec![];
+    write_report(&mut out, ListFormat::Json, &range_reports).unwrap();
+    let json = String::from_utf8(out).unwrap();
+    assert!(json.contains("\"id\": 123456712345671234567"));
+    assert!(json.contains("\"result\": 123456712345671234567"));
+    assert!(json.contains("\"unit\": \"1234567\""));
+}
+
+#[test]
+fn test_write_report_case_4() {
+    let range_reports = vec![RangeReport::new(
+        &from_u64_range(99..=255),
+        &RepetitionRule::Exactly(2),
+        &[10, 16],
+    )];
+    let mut out = vec![];
+    write_report(&mut out, ListFormat::Text, &range_reports).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    assert!(text.contains("  99 = 9 x 2\n  102 = 6 x 2 in base 16\n"));
+    assert!(text.contains("  255 = f x 2 in base 16\n"));
+}
+
+#[test]
+fn test_write_report_case_5() {
+    let range_reports = vec![RangeReport::new(
+        &from_u64_range(250..=255),
+        &RepetitionRule::Exactly(2),
+        &[10, 16],
+    )];
+    let mut out = vec![];
+    write_report(&mut out, ListFormat::Json, &range_reports).unwrap();
+    let parsed: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    let invalid_id = &parsed["ranges"][0]["invalid_ids"][0];
+    assert_eq!(invalid_id["id"], 255);
+    assert_eq!(invalid_id["unit"], "f");
+    assert_eq!(invalid_id["radix"], 16);
+}
+
+#[test]
+fn test_write_report_case_6() {
+    let rule = RepetitionRule::AtLeast(2);
+    let range_reports = vec![
+        RangeReport::new(&from_u64_range(11..=22), &rule, &[10]),
+        RangeReport::new(&from_u64_range(15..=30), &rule, &[10]),
+    ];
+    let mut out = vec![];
+    write_report(&mut out, ListFormat::Text, &range_reports).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "11-22: 2 invalid, sum 33\n  11 = 1 x 2\n  22 = 2 x 2\n15-30: 1 invalid, sum 22\n  22 = 2 x 2\nresult = 33\n"
+    );
+}
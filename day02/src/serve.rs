@@ -0,0 +1,519 @@
+// `day02 serve`: the same checks as the command line, one request per line.
+//
+// A client sends either a single decimal ID or a list of ranges in the same
+// syntax as the input files, and gets one line back:
+//
+//   1212            ->  invalid 1212 base=10 unit=12 period=2 repeats=2 periods=2
+//   1213            ->  valid 1213
+//   11-22,95-115    ->  sum 243
+//   11-22,15-30     ->  sum 33, 22 is in both ranges but counts once
+//   11-2x           ->  error byte 4: invalid character 'x' in number
+//
+// Every connection is served on its own thread until the client closes it
+// or stays silent for `idle_timeout`.
+// Past `max_connections` open connections, a new client gets an error line
+// and is disconnected; a failed accept is logged and the server goes on.
+// A request line longer than `MAXIMUM_REQUEST_LENGTH` gets an error and
+// closes the connection. With `enumerate` or several radixes every ID is
+// checked or listed, so such requests may cover at most
+// `MAXIMUM_CHECKED_IDS` IDs. The arithmetic sum grows with the square of
+// the number of digits, so no ID or range bound may be longer than
+// `MAXIMUM_ID_DIGITS` digits.
+
+use crate::id::IdRange;
+use crate::parse::{number_from_string, ranges_from_string};
+use crate::{RepetitionRule, ValidationResult, range_totals, validate};
+use num_bigint::BigUint;
+use rangeset::RangeSet;
+use std::error::Error;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+/// Longest request line, newline included, that the server reads.
+const MAXIMUM_REQUEST_LENGTH: u64 = 64 * 1024;
+
+/// Most IDs a range request may cover when its IDs are checked one by one.
+const MAXIMUM_CHECKED_IDS: u64 = 10_000_000;
+
+/// Most decimal digits of an ID or range bound in a request.
+const MAXIMUM_ID_DIGITS: usize = 100;
+
+/// The rules every request is checked against, fixed when the server starts.
+#[derive(Debug)]
+pub struct ServeOptions {
+    pub rule: RepetitionRule,
+    pub radixes: Vec<u32>,
+    pub enumerate: bool,
+    pub max_connections: usize,
+    pub idle_timeout: Duration,
+}
+
+/// A stream the server accepts, on which reads and writes can time out.
+trait Connection: Read + Write + Send + 'static {
+    fn set_timeouts(&self, timeout: Duration) -> io::Result<()>;
+}
+
+impl Connection for TcpStream {
+    fn set_timeouts(&self, timeout: Duration) -> io::Result<()> {
+        self.set_read_timeout(Some(timeout))?;
+        self.set_write_timeout(Some(timeout))
+    }
+}
+
+impl Connection for UnixStream {
+    fn set_timeouts(&self, timeout: Duration) -> io::Result<()> {
+        self.set_read_timeout(Some(timeout))?;
+        self.set_write_timeout(Some(timeout))
+    }
+}
+
+/// One of the `max_connections` connections that may be open at once;
+/// dropping it frees the place.
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl ConnectionSlot {
+    fn take(open_connections: &Arc<AtomicUsize>, max_connections: usize) -> Option<ConnectionSlot> {
+        open_connections
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |open| {
+                (open < max_connections).then_some(open + 1)
+            })
+            .ok()
+            .map(|_| ConnectionSlot(Arc::clone(open_connections)))
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+pub enum Listener {
+    Tcp(TcpListener),
+    Unix(UnixListener),
+}
+
+impl Listener {
+    /// Listens on localhost TCP; other addresses are refused since the
+    /// protocol has no authentication.
+    pub fn tcp(address: SocketAddr) -> Result<Listener, Box<dyn Error>> {
+        if !address.ip().is_loopback() {
+            return Err(format!("{} is not a localhost address", address).into());
+        }
+        Ok(Listener::Tcp(TcpListener::bind(address)?))
+    }
+
+    pub fn unix(path: &Path) -> io::Result<Listener> {
+        Ok(Listener::Unix(UnixListener::bind(path)?))
+    }
+
+    /// Accepts connections until the process is stopped.
+    pub fn serve(&self, options: ServeOptions) {
+        let options = Arc::new(options);
+        let open_connections = Arc::new(AtomicUsize::new(0));
+        match self {
+            Listener::Tcp(listener) => {
+                for stream in listener.incoming() {
+                    accept(stream, &options, &open_connections);
+                }
+            }
+            Listener::Unix(listener) => {
+                for stream in listener.incoming() {
+                    accept(stream, &options, &open_connections);
+                }
+            }
+        }
+    }
+}
+
+fn accept(
+    stream: io::Result<impl Connection>,
+    options: &Arc<ServeOptions>,
+    open_connections: &Arc<AtomicUsize>,
+) {
+    // A read or write that times out ends the connection and frees its slot.
+    let mut stream = match stream.and_then(|stream| {
+        stream.set_timeouts(options.idle_timeout)?;
+        Ok(stream)
+    }) {
+        Ok(stream) => stream,
+        Err(error) => {
+            eprintln!("accept failed: {}", error);
+            return;
+        }
+    };
+    let Some(slot) = ConnectionSlot::take(open_connections, options.max_connections) else {
+        let _ = writeln!(
+            stream,
+            "error too many connections, at most {}",
+            options.max_connections
+        );
+        return;
+    };
+    let options = Arc::clone(options);
+    thread::spawn(move || {
+        let _slot = slot;
+        if let Err(error) = handle_connection(stream, &options) {
+            eprintln!("connection closed: {}", error);
+        }
+    });
+}
+
+fn handle_connection(stream: impl Read + Write, options: &ServeOptions) -> io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        let length = (&mut reader)
+            .take(MAXIMUM_REQUEST_LENGTH)
+            .read_line(&mut line)?;
+        if length == 0 {
+            return Ok(());
+        }
+        if length as u64 == MAXIMUM_REQUEST_LENGTH && !line.ends_with('\n') {
+            let stream = reader.get_mut();
+            writeln!(
+                stream,
+                "error request longer than {} bytes",
+                MAXIMUM_REQUEST_LENGTH
+            )?;
+            return stream.flush();
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = respond(&line, options);
+        let stream = reader.get_mut();
+        writeln!(stream, "{}", response)?;
+        stream.flush()?;
+    }
+}
+
+fn respond(request: &str, options: &ServeOptions) -> String {
+    if request.contains('-') {
+        return match ranges_from_string(request) {
+            Ok(ranges) => {
+                if let Some(error) = ranges
+                    .iter()
+                    .find_map(|range| too_long(range.start()).or_else(|| too_long(range.end())))
+                {
+                    return error;
+                }
+                let ranges = RangeSet::from_iter(ranges);
+                let number_of_ids = ranges.cardinality();
+                let checks_each_id = options.enumerate || options.radixes.len() > 1;
+                if checks_each_id && number_of_ids > BigUint::from(MAXIMUM_CHECKED_IDS) {
+                    return format!(
+                        "error ranges cover {} IDs, at most {} can be checked one by one",
+                        number_of_ids, MAXIMUM_CHECKED_IDS
+                    );
+                }
+                format!("sum {}", sum(&ranges.into_ranges(), options))
+            }
+            Err(error) => format!("error {}", error),
+        };
+    }
+    match number_from_string(request) {
+        Ok(id) => too_long(&id).unwrap_or_else(|| check(&id, options)),
+        Err(error) => format!("error {}", error),
+    }
+}
+
+// The error response for an ID with more than `MAXIMUM_ID_DIGITS` digits.
+fn too_long(id: &BigUint) -> Option<String> {
+    let digits = id.to_string().len();
+    (digits > MAXIMUM_ID_DIGITS).then(|| {
+        format!(
+            "error ID has {} digits, at most {} are allowed",
+            digits, MAXIMUM_ID_DIGITS
+        )
+    })
+}
+
+fn sum(ranges: &[IdRange], options: &ServeOptions) -> BigUint {
+    range_totals(ranges, &options.rule, &options.radixes, options.enumerate)
+        .iter()
+        .map(|range_total| &range_total.sum)
+        .sum()
+}
+
+// Reports the first radix the ID is invalid in.
+fn check(id: &BigUint, options: &ServeOptions) -> String {
+    for &radix in &options.radixes {
+        if let ValidationResult::Invalid {
+            unit,
+            period,
+            repeats,
+            periods,
+        } = validate(&id.to_str_radix(radix), &options.rule)
+        {
+            let periods: Vec<String> = periods.iter().map(ToString::to_string).collect();
+            return format!(
+                "invalid {} base={} unit={} period={} repeats={} periods={}",
+                id,
+                radix,
+                unit,
+                period,
+                repeats,
+                periods.join(",")
+            );
+        }
+    }
+    format!("valid {}", id)
+}
+
+// --- respond ---
+
+#[cfg(test)]
+fn options(rule: RepetitionRule, radixes: Vec<u32>) -> ServeOptions {
+    ServeOptions {
+        rule,
+        radixes,
+        enumerate: false,
+        max_connections: 4,
+        idle_timeout: Duration::from_secs(10),
+    }
+}
+
+#[test]
+fn test_respond_case_1() {
+    let options = options(RepetitionRule::AtLeast(2), vec![10]);
+    assert_eq!(
+        respond("1212\n", &options),
+        "invalid 1212 base=10 unit=12 period=2 repeats=2 periods=2"
+    );
+    assert_eq!(
+        respond("11111111", &options),
+        "invalid 11111111 base=10 unit=1 period=1 repeats=8 periods=1,2,4"
+    );
+    assert_eq!(respond(" 1213 ", &options), "valid 1213");
+}
+
+#[test]
+fn test_respond_case_2() {
+    let options = options(RepetitionRule::AtLeast(2), vec![10]);
+    assert_eq!(respond("11-22,95-115\n", &options), "sum 243");
+    assert_eq!(respond("11-22,15-30", &options), "sum 33");
+    assert_eq!(
+        respond("11-2x", &options),
+        "error byte 4: invalid character 'x' in number"
+    );
+    assert_eq!(
+        respond("12a", &options),
+        "error byte 2: invalid character 'a' in number"
+    );
+}
+
+#[test]
+fn test_respond_case_3() {
+    let mut options = options(RepetitionRule::Exactly(2), vec![10, 16]);
+    assert_eq!(
+        respond("255", &options),
+        "invalid 255 base=16 unit=f period=1 repeats=2 periods=1"
+    );
+    assert_eq!(respond("11-22", &options), "sum 50");
+    options.enumerate = true;
+    assert_eq!(respond("11-22", &options), "sum 50");
+}
+
+#[test]
+fn test_respond_case_4() {
+    let mut options = options(RepetitionRule::AtLeast(2), vec![10, 16]);
+    assert_eq!(
+        respond("1-100000000000000000000", &options),
+        "error ranges cover 100000000000000000000 IDs, at most 10000000 can be checked one by one"
+    );
+    // Overlapping ranges are merged before they are counted.
+    assert_eq!(
+        respond("1-6000000,4000001-10000000", &options)
+            .split(' ')
+            .next(),
+        Some("sum")
+    );
+    options.radixes = vec![10];
+    assert_eq!(
+        respond("1-100000000000000000000", &options)
+            .split(' ')
+            .next(),
+        Some("sum")
+    );
+    options.enumerate = true;
+    assert!(respond("1-10000001", &options).starts_with("error ranges cover 10000001 IDs"));
+}
+
+// --- Listener ---
+
+#[test]
+fn test_listener_case_1() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        Listener::Tcp(listener).serve(options(RepetitionRule::AtLeast(2), vec![10]))
+    });
+    let stream = TcpStream::connect(address).unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut writer = stream;
+    writer.write_all(b"6464\n\n998-1012\n").unwrap();
+    let mut lines = vec![String::new(), String::new()];
+    for line in &mut lines {
+        reader.read_line(line).unwrap();
+    }
+    assert_eq!(
+        lines,
+        vec![
+            "invalid 6464 base=10 unit=64 period=2 repeats=2 periods=2\n",
+            "sum 2009\n"
+        ]
+    );
+}
+
+#[test]
+fn test_listener_case_2() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("day02.sock");
+    let listener = Listener::unix(&path).unwrap();
+    thread::spawn(move || listener.serve(options(RepetitionRule::AtLeast(2), vec![10])));
+    let stream = std::os::unix::net::UnixStream::connect(&path).unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut writer = stream;
+    writer.write_all(b"12\n").unwrap();
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    assert_eq!(line, "valid 12\n");
+    assert!(Listener::tcp("0.0.0.0:0".parse().unwrap()).is_err());
+}
+
+#[test]
+fn test_listener_case_3() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let mut options = options(RepetitionRule::AtLeast(2), vec![10]);
+    options.max_connections = 1;
+    thread::spawn(move || Listener::Tcp(listener).serve(options));
+    let ask = |stream: &TcpStream, request: &[u8]| {
+        (&*stream).write_all(request).unwrap();
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line).unwrap();
+        line
+    };
+    let first = TcpStream::connect(address).unwrap();
+    assert_eq!(
+        ask(&first, b"11\n"),
+        "invalid 11 base=10 unit=1 period=1 repeats=2 periods=1\n"
+    );
+    let second = TcpStream::connect(address).unwrap();
+    assert_eq!(
+        ask(&second, b"12\n"),
+        "error too many connections, at most 1\n"
+    );
+    drop(first);
+    // The slot is freed once the first connection's thread sees it closed.
+    let third = loop {
+        let stream = TcpStream::connect(address).unwrap();
+        let line = ask(&stream, b"12\n");
+        if line == "valid 12\n" {
+            break stream;
+        }
+        thread::sleep(std::time::Duration::from_millis(10));
+    };
+    assert_eq!(ask(&third, b"13\n"), "valid 13\n");
+}
+
+#[test]
+fn test_respond_case_5() {
+    let options = options(RepetitionRule::AtLeast(2), vec![10]);
+    let nines = "9".repeat(MAXIMUM_ID_DIGITS);
+    assert_eq!(
+        respond(&format!("1-{}", nines), &options).split(' ').next(),
+        Some("sum")
+    );
+    assert_eq!(
+        respond(&format!("1-{}9", nines), &options),
+        "error ID has 101 digits, at most 100 are allowed"
+    );
+    assert_eq!(
+        respond(&format!("1{}-1{}", nines, nines), &options),
+        "error ID has 101 digits, at most 100 are allowed"
+    );
+    assert_eq!(
+        respond(&format!("{}9", nines), &options),
+        "error ID has 101 digits, at most 100 are allowed"
+    );
+    assert_eq!(
+        respond(&format!("00{}", nines), &options).split(' ').next(),
+        Some("invalid")
+    );
+}
+
+#[test]
+fn test_listener_case_4() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let mut options = options(RepetitionRule::AtLeast(2), vec![10]);
+    options.max_connections = 1;
+    options.idle_timeout = Duration::from_millis(100);
+    thread::spawn(move || Listener::Tcp(listener).serve(options));
+    // An idle client holds the only slot until its read times out.
+    let idle = TcpStream::connect(address).unwrap();
+    thread::sleep(Duration::from_millis(300));
+    let stream = TcpStream::connect(address).unwrap();
+    (&stream).write_all(b"12\n").unwrap();
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line).unwrap();
+    assert_eq!(line, "valid 12\n");
+    drop(idle);
+}
+
+#[test]
+fn test_handle_connection_case_1() {
+    let mut request = b"12\n".to_vec();
+    request.extend(std::iter::repeat_n(b'1', MAXIMUM_REQUEST_LENGTH as usize));
+    request.extend(b"\n13\n");
+    let mut connection = MemoryStream {
+        input: io::Cursor::new(request),
+        output: vec![],
+    };
+    handle_connection(
+        &mut connection,
+        &options(RepetitionRule::AtLeast(2), vec![10]),
+    )
+    .unwrap();
+    assert_eq!(
+        String::from_utf8(connection.output).unwrap(),
+        format!(
+            "valid 12\nerror request longer than {} bytes\n",
+            MAXIMUM_REQUEST_LENGTH
+        )
+    );
+}
+
+// An in-memory stream that reads `input` and collects what is written.
+#[cfg(test)]
+struct MemoryStream {
+    input: io::Cursor<Vec<u8>>,
+    output: Vec<u8>,
+}
+
+#[cfg(test)]
+impl Read for MemoryStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.input.read(buf)
+    }
+}
+
+#[cfg(test)]
+impl Write for MemoryStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.output.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -1,15 +1,33 @@
+mod report;
+
 use clap::Parser;
+use report::{Bank, ReportFormat, write_report};
 use std::fs::File;
 use std::io::BufReader;
-use std::io::{BufRead, Result};
+use std::io::{self, BufRead, BufWriter, Result, Write};
+use std::process;
 
 #[derive(Parser, Debug)]
 struct Args {
     #[arg(short, long, default_value = "input.txt")]
     input_file: String,
+
+    /// How many batteries to turn on in each bank; 2 gives the part one answer.
+    #[arg(short, long, default_value_t = 12, value_parser = clap::value_parser!(u8).range(1..=38))]
+    digits: u8,
+
+    /// Print the chosen batteries of every bank, their positions and the joltage.
+    #[arg(long)]
+    report: bool,
+
+    /// Output format of --report.
+    #[arg(long, value_enum, default_value_t = ReportFormat::Table, requires = "report")]
+    format: ReportFormat,
 }
 
-fn max_digits_into_integer(input: &str, num_of_digits: usize) -> u128 {
+// Picks the digits that make the largest number, keeping their order.
+// Returns their indices in `input`.
+fn max_digits(input: &str, num_of_digits: usize) -> Vec<usize> {
     assert!(
         num_of_digits > 0,
         "requested digits should be greater than 1"
@@ -20,15 +38,15 @@ fn max_digits_into_integer(input: &str, num_of_digits: usize) -> u128 {
     );
 
     let total = input.len();
-    let mut stack: Vec<u8> = Vec::with_capacity(num_of_digits);
+    let bytes = input.as_bytes();
+    let mut stack: Vec<usize> = Vec::with_capacity(num_of_digits);
 
-    for (idx, byte) in input.bytes().enumerate() {
-        let num = byte - b'0';
+    for (idx, &byte) in bytes.iter().enumerate() {
         let remaining = total - idx;
 
         // pop out from the stack as many items as needed.
         while let Some(&top) = stack.last() {
-            if num > top && stack.len() + remaining > num_of_digits {
+            if byte > bytes[top] && stack.len() + remaining > num_of_digits {
                 stack.pop();
             } else {
                 break;
@@ -37,13 +55,30 @@ fn max_digits_into_integer(input: &str, num_of_digits: usize) -> u128 {
 
         // should I push the current item to the stack
         if stack.len() < num_of_digits {
-            stack.push(num);
+            stack.push(idx);
         }
     }
 
     stack
+}
+
+fn max_digits_into_integer(input: &str, num_of_digits: usize) -> u128 {
+    max_digits(input, num_of_digits)
         .into_iter()
-        .fold(0_u128, |acc, digit| acc * 10 + digit as u128)
+        .fold(0_u128, |acc, idx| {
+            acc * 10 + (input.as_bytes()[idx] - b'0') as u128
+        })
+}
+
+// Adds up the joltages, or returns `None` when the sum does not fit in a
+// u128; five banks of 38 nines are already too much.
+fn total_joltage(joltages: impl IntoIterator<Item = u128>) -> Option<u128> {
+    joltages.into_iter().try_fold(0_u128, u128::checked_add)
+}
+
+fn overflow() -> ! {
+    eprintln!("the sum of the joltages is larger than {}", u128::MAX);
+    process::exit(1);
 }
 
 fn read_lines(path: &str) -> Result<Vec<String>> {
@@ -54,11 +89,43 @@ fn read_lines(path: &str) -> Result<Vec<String>> {
 
 fn main() -> Result<()> {
     let args: Args = Args::parse();
-    let sum: u128 = read_lines(&args.input_file)?
-        .into_iter()
-        .filter(|l| !l.trim().is_empty())
-        .map(|line| max_digits_into_integer(&line, 12) as u128)
-        .sum();
+    let num_of_digits = args.digits as usize;
+    let mut lines = vec![];
+    for (idx, line) in read_lines(&args.input_file)?.into_iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if line.len() < num_of_digits {
+            eprintln!(
+                "line {} has {} batteries, fewer than the {} requested",
+                idx + 1,
+                line.len(),
+                num_of_digits
+            );
+            process::exit(1);
+        }
+        lines.push((idx + 1, line));
+    }
+
+    if args.report {
+        let banks: Vec<Bank> = lines
+            .iter()
+            .map(|(line_number, line)| Bank::new(*line_number, line, num_of_digits))
+            .collect();
+        let sum =
+            total_joltage(banks.iter().map(|bank| bank.joltage)).unwrap_or_else(|| overflow());
+        let mut out = BufWriter::new(io::stdout().lock());
+        write_report(&mut out, args.format, &banks, sum)?;
+        out.flush()?;
+        return Ok(());
+    }
+
+    let sum = total_joltage(
+        lines
+            .iter()
+            .map(|(_, line)| max_digits_into_integer(line, num_of_digits)),
+    )
+    .unwrap_or_else(|| overflow());
 
     println!("sum = {}", sum);
 
@@ -100,3 +167,39 @@ fn test_max_digits_into_integer_case_4() {
     let result = max_digits_into_integer(input, num_of_digits);
     assert_eq!(result, 434234234278);
 }
+
+#[test]
+fn test_max_digits_into_integer_case_5() {
+    assert_eq!(max_digits_into_integer("987654321111111", 2), 98);
+    assert_eq!(max_digits_into_integer("811111111111119", 2), 89);
+    assert_eq!(max_digits_into_integer("818181911112111", 2), 92);
+    assert_eq!(max_digits_into_integer("12", 2), 12);
+}
+
+// total_joltage()
+
+#[test]
+fn test_total_joltage_case_1() {
+    let nines = "9".repeat(38);
+    let joltage = max_digits_into_integer(&nines, 38);
+    assert_eq!(
+        total_joltage(vec![joltage; 3]),
+        Some(3 * (10_u128.pow(38) - 1))
+    );
+    assert_eq!(total_joltage(vec![joltage; 5]), None);
+    assert_eq!(total_joltage(vec![]), Some(0));
+}
+
+// max_digits()
+
+#[test]
+fn test_max_digits_case_1() {
+    let result = max_digits("234234234234278", 12);
+    assert_eq!(result, vec![2, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14]);
+}
+
+#[test]
+fn test_max_digits_case_2() {
+    assert_eq!(max_digits("818181911112111", 2), vec![6, 11]);
+    assert_eq!(max_digits("5", 1), vec![0]);
+}
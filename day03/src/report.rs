@@ -0,0 +1,143 @@
+// Output of `--report`: the batteries chosen in every bank.
+
+use crate::max_digits;
+use serde::Serialize;
+use std::io::{self, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum ReportFormat {
+    Table,
+    Json,
+}
+
+/// One line of the input with the batteries that give it the most joltage.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Bank {
+    pub line: usize,
+    pub digits: String,
+    pub indices: Vec<usize>,
+    pub joltage: u128,
+}
+
+impl Bank {
+    pub fn new(line: usize, batteries: &str, num_of_digits: usize) -> Bank {
+        let indices = max_digits(batteries, num_of_digits);
+        let digits: String = indices
+            .iter()
+            .map(|&idx| batteries.as_bytes()[idx] as char)
+            .collect();
+        Bank {
+            line,
+            joltage: digits.parse().unwrap(),
+            digits,
+            indices,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Report<'a> {
+    banks: &'a [Bank],
+    sum: u128,
+}
+
+/// Writes the banks and `sum`, the total of their joltages.
+pub fn write_report(
+    out: &mut impl Write,
+    format: ReportFormat,
+    banks: &[Bank],
+    sum: u128,
+) -> io::Result<()> {
+    match format {
+        ReportFormat::Table => {
+            // Leading zeros make the joltage shorter than the digits.
+            let width = banks
+                .iter()
+                .map(|bank| bank.digits.len())
+                .max()
+                .unwrap_or(0);
+            let digits_width = width.max("digits".len());
+            let joltage_width = width.max("joltage".len());
+            writeln!(
+                out,
+                "{:>6}  {:<digits_width$}  {:>joltage_width$}  indices",
+                "line", "digits", "joltage"
+            )?;
+            for bank in banks {
+                let indices: Vec<String> = bank.indices.iter().map(ToString::to_string).collect();
+                writeln!(
+                    out,
+                    "{:>6}  {:<digits_width$}  {:>joltage_width$}  {}",
+                    bank.line,
+                    bank.digits,
+                    bank.joltage,
+                    indices.join(",")
+                )?;
+            }
+            writeln!(out, "sum = {}", sum)
+        }
+        ReportFormat::Json => {
+            serde_json::to_writer_pretty(&mut *out, &Report { banks, sum })?;
+            writeln!(out)
+        }
+    }
+}
+
+// --- Bank::new ---
+
+#[test]
+fn test_bank_new_case_1() {
+    let bank = Bank::new(3, "234234234234278", 2);
+    assert_eq!(
+        bank,
+        Bank {
+            line: 3,
+            digits: "78".to_string(),
+            indices: vec![13, 14],
+            joltage: 78
+        }
+    );
+}
+
+// --- write_report ---
+
+#[cfg(test)]
+fn sample_banks() -> Vec<Bank> {
+    vec![
+        Bank::new(1, "987654321111111", 2),
+        Bank::new(2, "818181911112111", 2),
+    ]
+}
+
+#[test]
+fn test_write_report_case_1() {
+    let mut out = vec![];
+    write_report(&mut out, ReportFormat::Table, &sample_banks(), 190).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "  line  digits  joltage  indices\n     1  98           98  0,1\n     2  92           92  6,11\nsum = 190\n"
+    );
+}
+
+#[test]
+fn test_write_report_case_2() {
+    let mut out = vec![];
+    write_report(&mut out, ReportFormat::Json, &sample_banks(), 190).unwrap();
+    let parsed: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(parsed["sum"], 190);
+    assert_eq!(parsed["banks"][1]["line"], 2);
+    assert_eq!(parsed["banks"][1]["digits"], "92");
+    assert_eq!(parsed["banks"][1]["indices"], serde_json::json!([6, 11]));
+    assert_eq!(parsed["banks"][1]["joltage"], 92);
+}
+
+#[test]
+fn test_write_report_case_3() {
+    let banks = vec![Bank::new(1, "0012", 4)];
+    let mut out = vec![];
+    write_report(&mut out, ReportFormat::Table, &banks, 12).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "  line  digits  joltage  indices\n     1  0012         12  0,1,2,3\nsum = 12\n"
+    );
+}
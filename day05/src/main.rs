@@ -1,5 +1,4 @@
-use std::cmp::Ordering;
-use std::cmp::{max, min};
+use rangeset::RangeSet;
 use std::io::BufRead;
 use std::ops::RangeInclusive;
 
@@ -23,20 +22,12 @@ fn main() {
         }
     }
 
-    // sort
-    ranges.sort_by_key(|range| (*range.start(), *range.end()));
-
-    let non_overlapping_ranges = remove_overlapping_ranges(&ranges);
+    // sorted, with the overlapping ranges merged
+    let fresh_ranges: RangeSet<RangeBoundary> = ranges.into_iter().collect();
 
     let result = lines
-        .flatten() // converts Iterator<Result<String, _>> to Iterator<String>
-        .filter(|line| {
-            integer_position(
-                &non_overlapping_ranges,
-                line.parse::<RangeBoundary>().unwrap(),
-            )
-            .is_some()
-        })
+        .map_while(Result::ok)
+        .filter(|line| fresh_ranges.contains(&line.parse::<RangeBoundary>().unwrap()))
         .count();
 
     println!("result = {result}");
@@ -54,71 +45,6 @@ fn turn_into_range(line: &str) -> Option<IngredientsRange> {
     Some(start..=end)
 }
 
-// I have the way to sort ranges. But these ranges might be overlapping.
-// I can merge the overlapping and end having less ranges.
-//
-// Merging two ranges when they are overlapping
-
-fn merge_overlapping_ranges(
-    range1: &IngredientsRange,
-    range2: &IngredientsRange,
-) -> Result<IngredientsRange, String> {
-    if *range1.end() < *range2.start() - 1 || *range2.end() < *range1.start() - 1 {
-        return Err("Non overlapping ranges".to_string());
-    }
-    if *range1.end() == *range2.start() - 1 {
-        return Ok(*range1.start()..=*range2.end());
-    }
-    if *range2.end() == *range1.start() - 1 {
-        return Ok(*range2.start()..=*range1.end());
-    }
-    Ok(min(*range1.start(), *range2.start())..=max(*range1.end(), *range2.end()))
-}
-
-// Remove overlapping ranges by merging.
-// Note that this assumes that ranges are sorted by their `.start()`.
-
-fn remove_overlapping_ranges(ranges: &[IngredientsRange]) -> Vec<IngredientsRange> {
-    let mut non_overlapping_ranges: Vec<IngredientsRange> = vec![];
-    for (i, range) in ranges.iter().enumerate() {
-        if i == 0 {
-            non_overlapping_ranges.push(range.clone());
-            continue;
-        }
-        let merge_result = merge_overlapping_ranges(
-            &non_overlapping_ranges[non_overlapping_ranges.len() - 1],
-            &range,
-        );
-        if merge_result.is_ok() {
-            non_overlapping_ranges.pop();
-            non_overlapping_ranges.push(merge_result.unwrap());
-        } else {
-            non_overlapping_ranges.push(range.clone());
-        }
-    }
-    non_overlapping_ranges
-}
-
-// Given a sorted and non-overlapping sequence of ranges
-// and an integer, I can find the position of the range
-// the integer belongs to
-
-// integer_position
-
-fn integer_position(ranges: &[IngredientsRange], integer: RangeBoundary) -> Option<usize> {
-    ranges
-        .binary_search_by(|range| {
-            if integer < *range.start() {
-                Ordering::Greater
-            } else if integer > *range.end() {
-                Ordering::Less
-            } else {
-                Ordering::Equal
-            }
-        })
-        .ok()
-}
-
 // ------------------------------------------------------------------
 // Unit Tests
 // ------------------------------------------------------------------
@@ -188,288 +114,297 @@ fn test_sort_by_key_case_4() {
     assert_eq!(ranges, vec![3..=5, 3..=10]);
 }
 
-// merge_overlapping_ranges
+// merging two ranges in a RangeSet
 
 #[test]
-fn test_merge_overlapping_ranges_case_1() {
+fn test_range_set_merge_two_case_1() {
     let range1 = 1..=5;
     let range2 = 2..=6;
-    let non_overlapping_range = merge_overlapping_ranges(&range1, &range2);
-    assert_eq!(non_overlapping_range, Ok(1..=6));
+    let fresh_ranges: RangeSet<RangeBoundary> = [range1, range2].into_iter().collect();
+    assert_eq!(fresh_ranges.ranges(), [1..=6]);
 }
 
 #[test]
-fn test_merge_overlapping_ranges_case_2() {
+fn test_range_set_merge_two_case_2() {
     let range1 = 1..=5;
     let range2 = 6..=8;
-    let non_overlapping_range = merge_overlapping_ranges(&range1, &range2);
-    assert_eq!(non_overlapping_range, Ok(1..=8));
+    let fresh_ranges: RangeSet<RangeBoundary> = [range1, range2].into_iter().collect();
+    assert_eq!(fresh_ranges.ranges(), [1..=8]);
 }
 
 #[test]
-fn test_merge_overlapping_ranges_case_3() {
+fn test_range_set_merge_two_case_3() {
     let range1 = 6..=8;
     let range2 = 1..=5;
-    let non_overlapping_range = merge_overlapping_ranges(&range1, &range2);
-    assert_eq!(non_overlapping_range, Ok(1..=8));
+    let fresh_ranges: RangeSet<RangeBoundary> = [range1, range2].into_iter().collect();
+    assert_eq!(fresh_ranges.ranges(), [1..=8]);
 }
 
 #[test]
-fn test_merge_overlapping_ranges_case_4() {
+fn test_range_set_merge_two_case_4() {
     let range1 = 2..=6;
     let range2 = 1..=5;
-    let non_overlapping_range = merge_overlapping_ranges(&range1, &range2);
-    assert_eq!(non_overlapping_range, Ok(1..=6));
+    let fresh_ranges: RangeSet<RangeBoundary> = [range1, range2].into_iter().collect();
+    assert_eq!(fresh_ranges.ranges(), [1..=6]);
 }
 
 #[test]
-fn test_merge_overlapping_ranges_case_5() {
+fn test_range_set_merge_two_case_5() {
     let range1 = 1..=5;
     let range2 = 5..=8;
-    let non_overlapping_range = merge_overlapping_ranges(&range1, &range2);
-    assert_eq!(non_overlapping_range, Ok(1..=8));
+    let fresh_ranges: RangeSet<RangeBoundary> = [range1, range2].into_iter().collect();
+    assert_eq!(fresh_ranges.ranges(), [1..=8]);
 }
 
 #[test]
-fn test_merge_overlapping_ranges_case_6() {
+fn test_range_set_merge_two_case_6() {
     let range1 = 1..=5;
     let range2 = 6..=8;
-    let non_overlapping_range = merge_overlapping_ranges(&range1, &range2);
-    assert_eq!(non_overlapping_range, Ok(1..=8));
+    let fresh_ranges: RangeSet<RangeBoundary> = [range1, range2].into_iter().collect();
+    assert_eq!(fresh_ranges.ranges(), [1..=8]);
 }
 
-// remove_overlapping_ranges
+// merging any number of ranges in a RangeSet
 
 #[test]
-fn test_remove_overlapping_ranges_case_1() {
+fn test_range_set_merge_case_1() {
     let ranges: Vec<IngredientsRange> = vec![1..=5, 2..=6, 8..=20];
-    let non_overlapping_ranges = remove_overlapping_ranges(&ranges);
-    assert_eq!(non_overlapping_ranges, vec![1..=6, 8..=20]);
+    let non_overlapping_ranges = ranges.into_iter().collect::<RangeSet<_>>();
+    assert_eq!(non_overlapping_ranges.ranges(), [1..=6, 8..=20]);
 }
 
 #[test]
-fn test_remove_overlapping_ranges_case_2() {
+fn test_range_set_merge_case_2() {
     let ranges: Vec<IngredientsRange> = vec![1..=5, 8..=20];
-    let non_overlapping_ranges = remove_overlapping_ranges(&ranges);
-    assert_eq!(non_overlapping_ranges, vec![1..=5, 8..=20]);
+    let non_overlapping_ranges = ranges.into_iter().collect::<RangeSet<_>>();
+    assert_eq!(non_overlapping_ranges.ranges(), [1..=5, 8..=20]);
 }
 
 #[test]
-fn test_remove_overlapping_ranges_case_3() {
+fn test_range_set_merge_case_3() {
     let ranges: Vec<IngredientsRange> = vec![1..=5];
-    let non_overlapping_ranges = remove_overlapping_ranges(&ranges);
-    assert_eq!(non_overlapping_ranges, vec![1..=5]);
+    let non_overlapping_ranges = ranges.into_iter().collect::<RangeSet<_>>();
+    assert_eq!(non_overlapping_ranges.ranges(), [1..=5]);
 }
 
 #[test]
-fn test_remove_overlapping_ranges_case_4() {
+fn test_range_set_merge_case_4() {
     let ranges: Vec<IngredientsRange> = vec![];
-    let non_overlapping_ranges = remove_overlapping_ranges(&ranges);
-    assert_eq!(non_overlapping_ranges, vec![]);
+    let non_overlapping_ranges = ranges.into_iter().collect::<RangeSet<_>>();
+    assert!(non_overlapping_ranges.is_empty());
 }
 
 #[test]
-fn test_remove_overlapping_ranges_case_5() {
+fn test_range_set_merge_case_5() {
     let ranges: Vec<IngredientsRange> = vec![1..=5, 5..=8, 5..=9, 8..=10];
-    let non_overlapping_ranges = remove_overlapping_ranges(&ranges);
-    assert_eq!(non_overlapping_ranges, vec![1..=10]);
+    let non_overlapping_ranges = ranges.into_iter().collect::<RangeSet<_>>();
+    assert_eq!(non_overlapping_ranges.ranges(), [1..=10]);
 }
 
 #[test]
-fn test_remove_overlapping_ranges_case_6() {
+fn test_range_set_merge_case_6() {
     let ranges: Vec<IngredientsRange> = vec![1..=5, 5..=7, 5..=8, 9..=11];
-    let non_overlapping_ranges = remove_overlapping_ranges(&ranges);
-    assert_eq!(non_overlapping_ranges, vec![1..=11]);
+    let non_overlapping_ranges = ranges.into_iter().collect::<RangeSet<_>>();
+    assert_eq!(non_overlapping_ranges.ranges(), [1..=11]);
+}
+
+#[test]
+fn test_range_set_merge_case_7() {
+    // A range starting at 0 used to underflow when checking for a gap before it.
+    let ranges: Vec<IngredientsRange> = vec![4..=6, 0..=2, 3..=3];
+    let non_overlapping_ranges = ranges.into_iter().collect::<RangeSet<_>>();
+    assert_eq!(non_overlapping_ranges.ranges(), [0..=6]);
+    assert_eq!(non_overlapping_ranges.cardinality(), 7);
 }
 
 // sort and remove
 
 #[test]
-fn test_sort_and_remove_overlapping_ranges_case_1() {
+fn test_sort_and_range_set_merge_case_1() {
     let mut ranges: Vec<IngredientsRange> = vec![5..=7, 1..=5, 9..=11, 5..=8];
     ranges.sort_by_key(|range| (*range.start(), *range.end()));
-    let non_overlapping_ranges = remove_overlapping_ranges(&ranges);
-    assert_eq!(non_overlapping_ranges, vec![1..=11]);
+    let non_overlapping_ranges = ranges.into_iter().collect::<RangeSet<_>>();
+    assert_eq!(non_overlapping_ranges.ranges(), [1..=11]);
 }
 
-// integer position
+// position of an integer in a RangeSet
 
 #[test]
-fn test_integer_position_case_1() {
+fn test_range_set_position_case_1() {
     let mut ranges: Vec<IngredientsRange> = vec![5..=7, 1..=5, 9..=11, 5..=8];
 
     // need to sort
     ranges.sort_by_key(|range| (*range.start(), *range.end()));
 
-    // remove overlapping
-    let non_overlapping_ranges = remove_overlapping_ranges(&ranges); // this is : [1..=11]
+    // merge overlapping
+    let non_overlapping_ranges = ranges.into_iter().collect::<RangeSet<_>>(); // this is : [1..=11]
 
     let integer: RangeBoundary = 1;
-    let position = integer_position(&non_overlapping_ranges, integer);
+    let position = non_overlapping_ranges.position(&integer);
 
     assert_eq!(position, Some(0));
 
     let integer: RangeBoundary = 11;
-    let position = integer_position(&non_overlapping_ranges, integer);
+    let position = non_overlapping_ranges.position(&integer);
 
     assert_eq!(position, Some(0));
 
     let integer: RangeBoundary = 2;
-    let position = integer_position(&non_overlapping_ranges, integer);
+    let position = non_overlapping_ranges.position(&integer);
 
     assert_eq!(position, Some(0));
 }
 
 #[test]
-fn test_integer_position_case_2() {
+fn test_range_set_position_case_2() {
     let mut ranges: Vec<IngredientsRange> = vec![5..=7, 1..=5, 9..=11, 5..=8];
 
     // need to sort
     ranges.sort_by_key(|range| (*range.start(), *range.end()));
 
-    // remove overlapping
-    let non_overlapping_ranges = remove_overlapping_ranges(&ranges); // this is : [1..=11]
+    // merge overlapping
+    let non_overlapping_ranges = ranges.into_iter().collect::<RangeSet<_>>(); // this is : [1..=11]
 
     let integer: RangeBoundary = 1;
-    let position = integer_position(&non_overlapping_ranges, integer);
+    let position = non_overlapping_ranges.position(&integer);
 
     assert_eq!(position, Some(0));
 
     let integer: RangeBoundary = 11;
-    let position = integer_position(&non_overlapping_ranges, integer);
+    let position = non_overlapping_ranges.position(&integer);
 
     assert_eq!(position, Some(0));
 
     let integer: RangeBoundary = 2;
-    let position = integer_position(&non_overlapping_ranges, integer);
+    let position = non_overlapping_ranges.position(&integer);
 
     assert_eq!(position, Some(0));
 }
 
 #[test]
-fn test_integer_position_case_3() {
+fn test_range_set_position_case_3() {
     let mut ranges: Vec<IngredientsRange> = vec![];
 
     // need to sort
     ranges.sort_by_key(|range| (*range.start(), *range.end()));
 
-    // remove overlapping
-    let non_overlapping_ranges = remove_overlapping_ranges(&ranges);
+    // merge overlapping
+    let non_overlapping_ranges = ranges.into_iter().collect::<RangeSet<_>>();
 
     let integer: RangeBoundary = 1;
-    let position = integer_position(&non_overlapping_ranges, integer);
+    let position = non_overlapping_ranges.position(&integer);
 
     assert_eq!(position, None);
 }
 
 #[test]
-fn test_integer_position_case_4() {
+fn test_range_set_position_case_4() {
     let mut ranges: Vec<IngredientsRange> = vec![1..=5];
 
     // need to sort
     ranges.sort_by_key(|range| (*range.start(), *range.end()));
 
-    // remove overlapping
-    let non_overlapping_ranges = remove_overlapping_ranges(&ranges);
+    // merge overlapping
+    let non_overlapping_ranges = ranges.into_iter().collect::<RangeSet<_>>();
 
     let integer: RangeBoundary = 3;
-    let position = integer_position(&non_overlapping_ranges, integer);
+    let position = non_overlapping_ranges.position(&integer);
 
     assert_eq!(position, Some(0));
 }
 
 #[test]
-fn test_integer_position_case_5() {
+fn test_range_set_position_case_5() {
     let mut ranges: Vec<IngredientsRange> = vec![8..=15, 1..=5];
 
     // need to sort
     ranges.sort_by_key(|range| (*range.start(), *range.end()));
 
-    // remove overlapping
-    let non_overlapping_ranges = remove_overlapping_ranges(&ranges);
+    // merge overlapping
+    let non_overlapping_ranges = ranges.into_iter().collect::<RangeSet<_>>();
 
     let integer: RangeBoundary = 14;
-    let position = integer_position(&non_overlapping_ranges, integer);
+    let position = non_overlapping_ranges.position(&integer);
 
     assert_eq!(position, Some(1));
 }
 
 #[test]
-fn test_integer_position_case_6() {
+fn test_range_set_position_case_6() {
     let mut ranges: Vec<IngredientsRange> = vec![8..=15, 1..=5];
 
     // need to sort
     ranges.sort_by_key(|range| (*range.start(), *range.end()));
 
-    // remove overlapping
-    let non_overlapping_ranges = remove_overlapping_ranges(&ranges);
+    // merge overlapping
+    let non_overlapping_ranges = ranges.into_iter().collect::<RangeSet<_>>();
 
     let integer: RangeBoundary = 20;
-    let position = integer_position(&non_overlapping_ranges, integer);
+    let position = non_overlapping_ranges.position(&integer);
 
     assert_eq!(position, None);
 }
 
 #[test]
-fn test_integer_position_case_7() {
+fn test_range_set_position_case_7() {
     let mut ranges: Vec<IngredientsRange> = vec![8..=15, 1..=5];
 
     // need to sort
     ranges.sort_by_key(|range| (*range.start(), *range.end()));
 
-    // remove overlapping
-    let non_overlapping_ranges = remove_overlapping_ranges(&ranges);
+    // merge overlapping
+    let non_overlapping_ranges = ranges.into_iter().collect::<RangeSet<_>>();
 
     let integer: RangeBoundary = 0;
-    let position = integer_position(&non_overlapping_ranges, integer);
+    let position = non_overlapping_ranges.position(&integer);
 
     assert_eq!(position, None);
 }
 
 #[test]
-fn test_integer_position_case_8() {
+fn test_range_set_position_case_8() {
     let mut ranges: Vec<IngredientsRange> = vec![8..=15, 1..=5];
 
     // need to sort
     ranges.sort_by_key(|range| (*range.start(), *range.end()));
 
-    // remove overlapping
-    let non_overlapping_ranges = remove_overlapping_ranges(&ranges);
+    // merge overlapping
+    let non_overlapping_ranges = ranges.into_iter().collect::<RangeSet<_>>();
 
     let integer: RangeBoundary = 6;
-    let position = integer_position(&non_overlapping_ranges, integer);
+    let position = non_overlapping_ranges.position(&integer);
 
     assert_eq!(position, None);
 }
 
 #[test]
-fn test_integer_position_case_9() {
+fn test_range_set_position_case_9() {
     let mut ranges: Vec<IngredientsRange> = vec![8..=15, 1..=5];
 
     // need to sort
     ranges.sort_by_key(|range| (*range.start(), *range.end()));
 
-    // remove overlapping
-    let non_overlapping_ranges = remove_overlapping_ranges(&ranges);
+    // merge overlapping
+    let non_overlapping_ranges = ranges.into_iter().collect::<RangeSet<_>>();
 
     let integer: RangeBoundary = 5;
-    let position = integer_position(&non_overlapping_ranges, integer);
+    let position = non_overlapping_ranges.position(&integer);
 
     assert_eq!(position, Some(0));
 }
 
 #[test]
-fn test_integer_position_case_10() {
+fn test_range_set_position_case_10() {
     let mut ranges: Vec<IngredientsRange> = vec![8..=15, 1..=5];
 
     // need to sort
     ranges.sort_by_key(|range| (*range.start(), *range.end()));
 
-    // remove overlapping
-    let non_overlapping_ranges = remove_overlapping_ranges(&ranges);
+    // merge overlapping
+    let non_overlapping_ranges = ranges.into_iter().collect::<RangeSet<_>>();
 
     let integer: RangeBoundary = 8;
-    let position = integer_position(&non_overlapping_ranges, integer);
+    let position = non_overlapping_ranges.position(&integer);
 
     assert_eq!(position, Some(1));
 }
@@ -0,0 +1,375 @@
+// Sets of integers stored as sorted, disjoint inclusive ranges.
+//
+// A `RangeSet` never holds two ranges that overlap or touch: `1..=5` and
+// `6..=8` are kept as `1..=8`. Because of that, every value is in at most one
+// range, the cardinality is the sum of the range lengths, and membership is a
+// binary search.
+
+use std::cmp::Ordering;
+use std::iter::Sum;
+use std::ops::RangeInclusive;
+
+/// What a range set needs from the type of its values.
+pub trait Boundary: Ord + Clone {
+    /// Wide enough to count every value of the type.
+    type Count: Sum;
+
+    /// The next value, or `None` at the largest one.
+    fn successor(&self) -> Option<Self>;
+
+    /// The previous value, or `None` at the smallest one.
+    fn predecessor(&self) -> Option<Self>;
+
+    /// Number of values in a non-empty range.
+    fn count(range: &RangeInclusive<Self>) -> Self::Count;
+}
+
+impl Boundary for u64 {
+    type Count = u128;
+
+    fn successor(&self) -> Option<u64> {
+        self.checked_add(1)
+    }
+
+    fn predecessor(&self) -> Option<u64> {
+        self.checked_sub(1)
+    }
+
+    fn count(range: &RangeInclusive<u64>) -> u128 {
+        (range.end() - range.start()) as u128 + 1
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl Boundary for num_bigint::BigUint {
+    type Count = num_bigint::BigUint;
+
+    fn successor(&self) -> Option<Self> {
+        Some(self + 1_u32)
+    }
+
+    fn predecessor(&self) -> Option<Self> {
+        (*self != Self::ZERO).then(|| self - 1_u32)
+    }
+
+    fn count(range: &RangeInclusive<Self>) -> Self {
+        range.end() - range.start() + 1_u32
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeSet<T: Boundary> {
+    ranges: Vec<RangeInclusive<T>>,
+}
+
+impl<T: Boundary> Default for RangeSet<T> {
+    fn default() -> Self {
+        RangeSet { ranges: vec![] }
+    }
+}
+
+// Whether `next`, which does not start before `last`, overlaps or touches it.
+fn joins<T: Boundary>(last: &RangeInclusive<T>, next: &RangeInclusive<T>) -> bool {
+    match last.end().successor() {
+        Some(after_last) => *next.start() <= after_last,
+        None => true,
+    }
+}
+
+impl<T: Boundary> RangeSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The ranges in increasing order, none of them overlapping or touching.
+    pub fn ranges(&self) -> &[RangeInclusive<T>] {
+        &self.ranges
+    }
+
+    pub fn into_ranges(self) -> Vec<RangeInclusive<T>> {
+        self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Adds a range, merging it with the ranges it overlaps or touches.
+    /// Empty ranges such as `5..=3` add nothing.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let mut ranges = std::mem::take(&mut self.ranges);
+        ranges.push(range);
+        *self = ranges.into_iter().collect();
+    }
+
+    /// Index in `ranges()` of the range that holds `value`, if any.
+    pub fn position(&self, value: &T) -> Option<usize> {
+        self.ranges
+            .binary_search_by(|range| {
+                if value < range.start() {
+                    Ordering::Greater
+                } else if value > range.end() {
+                    Ordering::Less
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .ok()
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.position(value).is_some()
+    }
+
+    /// How many values the set holds.
+    pub fn cardinality(&self) -> T::Count {
+        self.ranges.iter().map(T::count).sum()
+    }
+
+    pub fn union(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        self.ranges.iter().chain(&other.ranges).cloned().collect()
+    }
+
+    pub fn intersection(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut ranges = vec![];
+        let (mut left, mut right) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        while let (Some(a), Some(b)) = (left.peek(), right.peek()) {
+            let start = a.start().max(b.start());
+            let end = a.end().min(b.end());
+            if start <= end {
+                ranges.push(start.clone()..=end.clone());
+            }
+            // The range that ends first cannot meet anything further on.
+            if a.end() < b.end() {
+                left.next();
+            } else {
+                right.next();
+            }
+        }
+        RangeSet { ranges }
+    }
+
+    /// The values of `bounds` that are not in the set.
+    pub fn complement(&self, bounds: RangeInclusive<T>) -> RangeSet<T> {
+        let mut ranges = vec![];
+        if bounds.is_empty() {
+            return RangeSet { ranges };
+        }
+        let (start, end) = bounds.into_inner();
+        // The smallest value not yet accounted for, `None` past the largest.
+        let mut next = Some(start);
+        for range in &self.ranges {
+            let Some(from) = next.clone() else {
+                break;
+            };
+            if *range.start() > end {
+                break;
+            }
+            if *range.end() < from {
+                continue;
+            }
+            if *range.start() > from {
+                let before = range
+                    .start()
+                    .predecessor()
+                    .expect("start is above another value");
+                ranges.push(from..=before);
+            }
+            next = range.end().successor();
+        }
+        if let Some(from) = next
+            && from <= end
+        {
+            ranges.push(from..=end);
+        }
+        RangeSet { ranges }
+    }
+
+    pub fn difference(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        match (self.ranges.first(), self.ranges.last()) {
+            (Some(first), Some(last)) => {
+                let hull = first.start().clone()..=last.end().clone();
+                self.intersection(&other.complement(hull))
+            }
+            _ => RangeSet::new(),
+        }
+    }
+}
+
+impl<T: Boundary> FromIterator<RangeInclusive<T>> for RangeSet<T> {
+    /// Sorts the ranges and merges the ones that overlap or touch.
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut ranges: Vec<RangeInclusive<T>> =
+            iter.into_iter().filter(|range| !range.is_empty()).collect();
+        ranges.sort_by(|a, b| (a.start(), a.end()).cmp(&(b.start(), b.end())));
+        let mut merged: Vec<RangeInclusive<T>> = vec![];
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if joins(last, &range) => {
+                    if range.end() > last.end() {
+                        *last = last.start().clone()..=range.end().clone();
+                    }
+                }
+                _ => merged.push(range),
+            }
+        }
+        RangeSet { ranges: merged }
+    }
+}
+
+// --- from_iter ---
+
+#[cfg(test)]
+fn set(ranges: Vec<RangeInclusive<u64>>) -> RangeSet<u64> {
+    ranges.into_iter().collect()
+}
+
+#[test]
+fn test_from_iter_case_1() {
+    let ranges = set(vec![
+        95..=115,
+        11..=22,
+        100..=120,
+        20..=30,
+        31..=40,
+        200..=210,
+    ]);
+    assert_eq!(ranges.ranges(), [11..=40, 95..=120, 200..=210]);
+}
+
+#[test]
+fn test_from_iter_case_2() {
+    assert_eq!(
+        set(vec![5..=u64::MAX, 0..=4, 10..=20]).ranges(),
+        [0..=u64::MAX]
+    );
+    assert_eq!(set(vec![1..=5, 5..=8, 5..=9, 8..=10]).ranges(), [1..=10]);
+    #[allow(clippy::reversed_empty_ranges)]
+    let empty = set(vec![5..=3]);
+    assert!(empty.is_empty());
+    assert!(set(vec![]).is_empty());
+}
+
+#[test]
+fn test_insert_case_1() {
+    let mut ranges = set(vec![1..=5, 10..=12]);
+    ranges.insert(6..=9);
+    assert_eq!(ranges.ranges(), [1..=12]);
+    ranges.insert(20..=20);
+    assert_eq!(ranges.ranges(), [1..=12, 20..=20]);
+}
+
+// --- position ---
+
+#[test]
+fn test_position_case_1() {
+    let ranges = set(vec![8..=15, 1..=5]);
+    assert_eq!(ranges.position(&0), None);
+    assert_eq!(ranges.position(&5), Some(0));
+    assert_eq!(ranges.position(&6), None);
+    assert_eq!(ranges.position(&8), Some(1));
+    assert_eq!(ranges.position(&20), None);
+    assert!(ranges.contains(&14));
+    assert!(!RangeSet::new().contains(&1_u64));
+}
+
+// --- cardinality ---
+
+#[test]
+fn test_cardinality_case_1() {
+    assert_eq!(
+        set(vec![3..=5, 10..=14, 16..=20, 12..=18]).cardinality(),
+        3 + 11
+    );
+    assert_eq!(set(vec![0..=u64::MAX]).cardinality(), u64::MAX as u128 + 1);
+    assert_eq!(set(vec![]).cardinality(), 0);
+}
+
+// --- union ---
+
+#[test]
+fn test_union_case_1() {
+    let a = set(vec![1..=5, 20..=30]);
+    let b = set(vec![6..=8, 25..=40, 50..=60]);
+    assert_eq!(a.union(&b).ranges(), [1..=8, 20..=40, 50..=60]);
+    assert_eq!(a.union(&RangeSet::new()), a);
+}
+
+// --- intersection ---
+
+#[test]
+fn test_intersection_case_1() {
+    let a = set(vec![1..=10, 20..=30, 40..=50]);
+    let b = set(vec![5..=25, 30..=45, 60..=70]);
+    assert_eq!(
+        a.intersection(&b).ranges(),
+        [5..=10, 20..=25, 30..=30, 40..=45]
+    );
+    assert!(a.intersection(&set(vec![11..=19])).is_empty());
+}
+
+// --- complement ---
+
+#[test]
+fn test_complement_case_1() {
+    let a = set(vec![3..=5, 8..=10]);
+    assert_eq!(a.complement(0..=20).ranges(), [0..=2, 6..=7, 11..=20]);
+    assert_eq!(a.complement(4..=9).ranges(), [6..=7]);
+    assert_eq!(a.complement(3..=5).ranges(), []);
+    assert_eq!(RangeSet::new().complement(1..=2).ranges(), [1..=2]);
+}
+
+#[test]
+fn test_complement_case_2() {
+    let a = set(vec![0..=5, u64::MAX - 1..=u64::MAX]);
+    assert_eq!(a.complement(0..=u64::MAX).ranges(), [6..=u64::MAX - 2]);
+    assert_eq!(
+        set(vec![0..=u64::MAX]).complement(0..=u64::MAX).ranges(),
+        []
+    );
+}
+
+// --- difference ---
+
+#[test]
+fn test_difference_case_1() {
+    let a = set(vec![1..=10, 20..=30]);
+    let b = set(vec![0..=2, 5..=6, 25..=100]);
+    assert_eq!(a.difference(&b).ranges(), [3..=4, 7..=10, 20..=24]);
+    assert_eq!(a.difference(&a).ranges(), []);
+    assert_eq!(RangeSet::new().difference(&a).ranges(), []);
+}
+
+#[test]
+fn test_difference_case_2() {
+    // |A| = |A ∩ B| + |A \ B| for a handful of overlapping layouts.
+    let a = set(vec![0..=100, 150..=160, 300..=400]);
+    for b in [
+        set(vec![50..=155]),
+        set(vec![0..=1000]),
+        set(vec![101..=149, 161..=299]),
+    ] {
+        assert_eq!(
+            a.cardinality(),
+            a.intersection(&b).cardinality() + a.difference(&b).cardinality()
+        );
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+#[test]
+fn test_big_boundary_case_1() {
+    use num_bigint::BigUint;
+    let big = |value: u64| BigUint::from(value);
+    let wide = big(u64::MAX) * big(10);
+    let a: RangeSet<BigUint> = [big(0)..=wide.clone(), big(5)..=big(7)]
+        .into_iter()
+        .collect();
+    assert_eq!(a.cardinality(), &wide + 1_u32);
+    let holes = a.complement(big(0)..=&wide + 10_u32);
+    assert_eq!(holes.ranges(), [&wide + 1_u32..=&wide + 10_u32]);
+    assert_eq!(big(0).predecessor(), None);
+}